@@ -14,7 +14,7 @@ use ethereum_types::H256;
 use rustc_hex::FromHex;
 
 use common_types::bytes::ToPretty;
-use goshen_chain::{state_transition, state_transition_to_header};
+use goshen_chain::execute_transition;
 use hash_db::HashDB;
 use riscv_db::RiscvDB;
 
@@ -23,8 +23,8 @@ pub extern "C" fn _start() {
     riscv_evm::runtime::debug("start");
     let mut db = RiscvDB::new(&[]);
     let hash = H256::from(riscv_evm::runtime::input());
-    let header = state_transition_to_header(db, hash);
-    riscv_evm::runtime::ret(header.hash().0);
+    let output = execute_transition(db, hash);
+    riscv_evm::runtime::ret(output.output_root().hash().0);
 }
 
 #[panic_handler]
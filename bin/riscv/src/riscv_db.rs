@@ -37,6 +37,10 @@ impl HashDB<KeccakHasher, DBValue> for RiscvDB {
         }
     }
 
+    fn value_len(&self, key: &H256) -> Option<usize> {
+        Some(riscv_evm::runtime::preimage_len(key.0))
+    }
+
     fn insert(&mut self, value: &[u8]) -> H256 {
         keccak(value)
     }
@@ -7,7 +7,7 @@ use rustc_hex::FromHex;
 use clap::Parser;
 use common_types::bytes::ToPretty;
 use ethcore::state::backend::ProofCheck;
-use goshen_chain::state_transition;
+use goshen_chain::execute_transition;
 use hash_db::HashDB;
 
 #[derive(Parser)]
@@ -18,7 +18,7 @@ struct Cli {
     /// entry hash
     #[arg(long, short)]
     input: String,
-    /// batch state(hash of end block that batch included)
+    /// output root of the transition, see `goshen_chain::output::OutputRoot`
     #[arg(long, short)]
     output: String,
     /// file path of batch data
@@ -38,6 +38,6 @@ fn main() {
     let output = if cli.output.starts_with("0x") { &cli.output[2..] } else { &cli.output };
 
     let hash = H256::from_str(input).unwrap();
-    let header = state_transition(db, hash);
-    assert_eq!(output, header.to_hex());
+    let result = execute_transition(db, hash);
+    assert_eq!(output, result.output_root().hash().to_hex());
 }
@@ -103,6 +103,12 @@ pub trait HashDB<H: Hasher, T>: AsHashDB<H, T> {
     /// Check for the existance of a hash-key.
     fn contains(&self, key: &H::Out) -> bool;
 
+    /// Length of the bytes that hash to `key` without copying them, None if the
+    /// backend can't tell it cheaply.
+    fn value_len(&self, _key: &H::Out) -> Option<usize> {
+        None
+    }
+
     /// Insert a datum item into the DB and return the datum's hash for a later lookup. Insertions
     /// are counted and the equivalent number of `remove()`s must be performed before the data
     /// is considered dead.
@@ -42,7 +42,9 @@ use ethereum_types::{Address, Bloom, H256, U256};
 
 use crate::engines::EthEngine;
 use crate::error::{BlockError, Error};
+use crate::executed::ExecutionError;
 use crate::factory::Factories;
+use crate::machine::{Fees, StatePatch};
 use crate::state::State;
 use crate::state_db::StateDB;
 use crate::trace::Tracing;
@@ -55,6 +57,8 @@ use hash::keccak;
 use rlp::{encode_list, RlpStream};
 use types::header::Header;
 use types::receipt::{TransactionOutcome, TypedReceipt};
+#[cfg(feature = "supply-check")]
+use types::state_diff::StateDiff;
 use types::transaction::{Error as TransactionError, SignedTransaction};
 
 /// Block that is ready for transactions to be added.
@@ -106,6 +110,15 @@ pub struct ExecutedBlock {
     pub traces: Tracing,
     /// Hashes of last 256 blocks.
     pub last_hashes: Arc<LastHashes>,
+    /// Irregular state changes applied when the block was opened.
+    pub state_patches: Vec<StatePatch>,
+    /// Transaction fees collected over the block.
+    pub fees: Fees,
+    /// Gas used by the enqueued transactions of the block.
+    pub enqueued_gas_used: U256,
+    /// Balance changes of the block, taken when it closes.
+    #[cfg(feature = "supply-check")]
+    pub balance_diff: Option<StateDiff>,
 }
 
 impl ExecutedBlock {
@@ -120,6 +133,11 @@ impl ExecutedBlock {
             state: state,
             traces: if tracing { Tracing::enabled() } else { Tracing::Disabled },
             last_hashes: last_hashes,
+            state_patches: Vec::new(),
+            fees: Fees::default(),
+            enqueued_gas_used: U256::zero(),
+            #[cfg(feature = "supply-check")]
+            balance_diff: None,
         }
     }
 
@@ -243,13 +261,31 @@ impl<'x> OpenBlock<'x> {
             return Err(TransactionError::AlreadyImported.into());
         }
 
+        let env_info = self.block.env_info();
+        // enqueued transactions only get their share of the block gas
+        let enqueued_gas_limit = if t.is_enqueued() {
+            self.engine.machine().enqueued_gas_limit(env_info.number)
+        } else {
+            None
+        };
+        if let Some(gas_limit) = enqueued_gas_limit {
+            let gas_used = self.block.enqueued_gas_used;
+            if gas_used + t.tx().gas > gas_limit {
+                return Err(ExecutionError::BlockGasLimitReached {
+                    gas_limit,
+                    gas_used,
+                    gas: t.tx().gas,
+                }
+                .into());
+            }
+        }
+
         // adjust difficulty
-        if t.is_enqueued() {
+        if t.is_enqueued() && self.engine.enqueued_difficulty() {
             let difficulty = self.block.header.difficulty();
             self.block.header.set_difficulty(*difficulty + 1);
         }
 
-        let env_info = self.block.env_info();
         let outcome = self.block.state.apply(
             &env_info,
             self.engine.machine(),
@@ -265,6 +301,10 @@ impl<'x> OpenBlock<'x> {
         if let Tracing::Enabled(ref mut traces) = self.block.traces {
             traces.push(outcome.trace.into());
         }
+        self.block.fees += outcome.fees;
+        if enqueued_gas_limit.is_some() {
+            self.block.enqueued_gas_used += outcome.receipt.gas_used - env_info.gas_used;
+        }
         self.block.receipts.push(outcome.receipt);
         Ok(self.block.receipts.last().expect("receipt just pushed; qed"))
     }
@@ -329,6 +369,12 @@ impl<'x> OpenBlock<'x> {
         // t_nb 8.5.1 engine applies block rewards (Ethash and AuRa do.Clique is empty)
         s.engine.on_close_block(&mut s.block)?;
 
+        // the parent state is only readable before the commit
+        #[cfg(feature = "supply-check")]
+        {
+            s.block.balance_diff = Some(s.block.state.balance_diff()?);
+        }
+
         // t_nb 8.5.2 commit account changes from cache to tree
         s.block.state.commit()?;
 
@@ -105,6 +105,9 @@ impl<'a> EvmTestClient<'a> {
             ForkSpec::Berlin => Some(ethereum::new_berlin_test()),
             ForkSpec::London => Some(ethereum::new_london_test()),
             ForkSpec::BerlinToLondonAt5 => Some(ethereum::new_berlin_to_london_test()),
+            ForkSpec::Shanghai => Some(ethereum::new_shanghai_test()),
+            ForkSpec::Cancun => Some(ethereum::new_cancun_test()),
+            ForkSpec::Prague => Some(ethereum::new_prague_test()),
             ForkSpec::FrontierToHomesteadAt5
             | ForkSpec::HomesteadToDaoAt5
             | ForkSpec::HomesteadToEIP150At5
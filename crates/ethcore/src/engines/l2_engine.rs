@@ -1,27 +1,142 @@
-use crate::engines::Engine;
+use crate::block::ExecutedBlock;
+use crate::engines::{block_reward, Engine};
+use crate::error::BlockError;
 use crate::machine::Machine;
+use builtin::MessagePasser;
 use core::cmp::max;
-use types::header::Header;
+use ethereum_types::{Address, H256, U256};
+use types::header::{Header, L2SealFields};
+use triehash::ordered_trie_root;
+use types::l2_cfg::{L2_BLOCK_MAX_GAS_LIMIT, L2_BLOCK_MIN_GAS_LIMIT, L2_CROSS_LAYER_WITNESS};
+use types::log_entry::LogEntry;
+use types::receipt::TypedReceipt;
+use types::transaction::SignedTransaction;
+use unexpected::{Mismatch, OutOfBounds};
+
+/// Layout of the seal of blocks produced by `L2Seal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SealLayout {
+    /// ethash styled `[mix_hash, nonce]` seal, the header difficulty counts the enqueued
+    /// transactions.
+    Legacy,
+    /// versioned `L2SealFields`.
+    Fields,
+}
 
 /// An engine which does not provide any consensus mechanism, just seals blocks internally.
 pub struct L2Seal<M> {
     timestamp: u64,
     machine: M,
+    layout: SealLayout,
 }
 
 impl<M> L2Seal<M> {
-    /// Returns new instance of L2Seal over the given state machine.
+    /// Returns new instance of L2Seal over the given state machine, sealing with `L2SealFields`.
     pub fn new(timestamp: u64, machine: M) -> Self {
-        L2Seal { timestamp, machine }
+        L2Seal { timestamp, machine, layout: SealLayout::Fields }
     }
 
     /// set timestamp
     pub fn set_timestamp(&mut self, time: u64) {
         self.timestamp = time;
     }
+
+    /// set the seal layout of produced blocks
+    pub fn set_seal_layout(&mut self, layout: SealLayout) {
+        self.layout = layout;
+    }
+}
+
+/// Event emitted by the L2 cross layer witness for every message sent to L1.
+pub fn message_sent_event_id() -> H256 {
+    MessagePasser::event_id()
+}
+
+/// Compute the seal of a block from the seal of its parent and the executed transactions.
+///
+/// The message index and root follow the last `MessageSent` event of `l2_witness_layer`, the
+/// queue index is advanced by the number of enqueued transactions.
+pub fn compute_l2_seal(
+    parent: &L2SealFields, transactions: &[SignedTransaction], receipts: &[TypedReceipt],
+    l2_witness_layer: Address,
+) -> L2SealFields {
+    let mut seal = parent.clone();
+    seal.queue_index += transactions.iter().filter(|t| t.is_enqueued()).count() as u64;
+    if let Some(log) = l2_messages(receipts, l2_witness_layer).last() {
+        seal.message_index = U256::from(log.topics[1].as_bytes()).low_u64();
+        seal.message_root = H256::from_slice(&log.data[0..32]);
+    }
+    seal
+}
+
+/// Cross layer messages sent to L1 in a block, as `MessageSent` logs in execution order.
+pub fn l2_messages<'a>(
+    receipts: &'a [TypedReceipt], l2_witness_layer: Address,
+) -> impl Iterator<Item = &'a LogEntry> + 'a {
+    let event_id = message_sent_event_id();
+    receipts.iter().flat_map(|r| r.logs.iter()).filter(move |log| {
+        log.address == l2_witness_layer
+            && log.topics.len() >= 2
+            && log.data.len() >= 32
+            && log.topics[0] == event_id
+    })
+}
+
+/// Root of cross layer messages, the ordered trie root of the RLP encoded logs.
+pub fn l2_messages_root<'a>(messages: impl IntoIterator<Item = &'a LogEntry>) -> H256 {
+    ordered_trie_root(messages.into_iter().map(|log| rlp::encode(log).to_vec()))
+}
+
+fn verify_seal_format(header: &Header, layout: SealLayout) -> Result<(), BlockError> {
+    let expected = match layout {
+        SealLayout::Legacy => L2SealFields::LEGACY_FIELDS,
+        SealLayout::Fields => L2SealFields::FIELDS,
+    };
+    if header.seal().len() != expected {
+        return Err(BlockError::InvalidSealArity(Mismatch {
+            expected,
+            found: header.seal().len(),
+        }));
+    }
+    header.l2_seal().map_err(|_| BlockError::InvalidSeal)?;
+    Ok(())
+}
+
+/// Check that the seal of an executed block matches the one computed from its parent seal and
+/// the executed transactions.
+pub fn verify_l2_seal_messages(
+    header: &Header, parent: &Header, transactions: &[SignedTransaction],
+    receipts: &[TypedReceipt], l2_witness_layer: Address,
+) -> Result<(), BlockError> {
+    let seal = header.l2_seal().map_err(|_| BlockError::InvalidSeal)?;
+    // a parent without L2 seal (genesis) starts the counters at zero
+    let parent_seal = parent.l2_seal().unwrap_or_default();
+    let expected = compute_l2_seal(&parent_seal, transactions, receipts, l2_witness_layer);
+    if seal.queue_index != expected.queue_index {
+        return Err(BlockError::InvalidQueueIndex(Mismatch {
+            expected: expected.queue_index,
+            found: seal.queue_index,
+        }));
+    }
+    if seal.message_root != expected.message_root {
+        return Err(BlockError::MismatchedH256SealElement(Mismatch {
+            expected: expected.message_root,
+            found: seal.message_root,
+        }));
+    }
+    if seal.message_index != expected.message_index {
+        return Err(BlockError::InvalidMessageIndex(Mismatch {
+            expected: expected.message_index,
+            found: seal.message_index,
+        }));
+    }
+    Ok(())
 }
 
-impl<M: Machine> Engine<M> for L2Seal<M> {
+impl<M: Machine> Engine<M> for L2Seal<M>
+where
+    M::Error: From<BlockError>,
+{
     fn name(&self) -> &str {
         "L2Seal"
     }
@@ -30,10 +145,117 @@ impl<M: Machine> Engine<M> for L2Seal<M> {
         &self.machine
     }
 
-    fn verify_local_seal(&self, _header: &Header) -> Result<(), M::Error> {
+    fn seal_fields(&self, _header: &Header) -> usize {
+        match self.layout {
+            SealLayout::Legacy => L2SealFields::LEGACY_FIELDS,
+            SealLayout::Fields => L2SealFields::FIELDS,
+        }
+    }
+
+    fn enqueued_difficulty(&self) -> bool {
+        self.layout == SealLayout::Legacy
+    }
+
+    fn on_close_block(&self, block: &mut ExecutedBlock) -> Result<(), M::Error> {
+        let rewards = self.machine.fee_rewards(block);
+        if rewards.is_empty() {
+            return Ok(());
+        }
+        block_reward::apply_block_rewards(&rewards, block, &self.machine)
+    }
+
+    fn verify_local_seal(&self, header: &Header) -> Result<(), M::Error> {
+        verify_seal_format(header, self.layout).map_err(Into::into)
+    }
+
+    fn verify_block_basic(&self, header: &Header) -> Result<(), M::Error> {
+        verify_seal_format(header, self.layout)?;
+
+        let min_gas = U256::from(L2_BLOCK_MIN_GAS_LIMIT);
+        let max_gas = U256::from(L2_BLOCK_MAX_GAS_LIMIT);
+        if header.gas_limit() < &min_gas || header.gas_limit() > &max_gas {
+            return Err(BlockError::InvalidGasLimit(OutOfBounds {
+                min: Some(min_gas),
+                max: Some(max_gas),
+                found: *header.gas_limit(),
+            })
+            .into());
+        }
+        if header.gas_used() > header.gas_limit() {
+            return Err(BlockError::TooMuchGasUsed(OutOfBounds {
+                min: None,
+                max: Some(*header.gas_limit()),
+                found: *header.gas_used(),
+            })
+            .into());
+        }
         Ok(())
     }
 
+    fn verify_block_family(&self, header: &Header, parent: &Header) -> Result<(), M::Error> {
+        if header.parent_hash() != &parent.hash() {
+            return Err(BlockError::UnknownParent(*header.parent_hash()).into());
+        }
+        if header.number() != parent.number() + 1 {
+            return Err(BlockError::InvalidNumber(Mismatch {
+                expected: parent.number() + 1,
+                found: header.number(),
+            })
+            .into());
+        }
+        if !self.is_timestamp_valid(header.timestamp(), parent.timestamp()) {
+            return Err(BlockError::InvalidTimestamp(OutOfBounds {
+                min: Some(parent.timestamp()),
+                max: None,
+                found: header.timestamp(),
+            })
+            .into());
+        }
+
+        // queue and message counters never go back, the exact values are checked against the
+        // block body by `verify_l2_seal_messages`.
+        let seal = header.l2_seal().map_err(|_| BlockError::InvalidSeal)?;
+        // a parent without L2 seal (genesis) starts the counters at zero
+        let parent_seal = parent.l2_seal().unwrap_or_default();
+        if seal.queue_index < parent_seal.queue_index {
+            return Err(BlockError::InvalidQueueIndex(Mismatch {
+                expected: parent_seal.queue_index,
+                found: seal.queue_index,
+            })
+            .into());
+        }
+        if seal.message_index < parent_seal.message_index {
+            return Err(BlockError::InvalidMessageIndex(Mismatch {
+                expected: parent_seal.message_index,
+                found: seal.message_index,
+            })
+            .into());
+        }
+        // a block without cross layer message keeps the root of its parent
+        if seal.message_index == parent_seal.message_index
+            && seal.message_root != parent_seal.message_root
+        {
+            return Err(BlockError::MismatchedH256SealElement(Mismatch {
+                expected: parent_seal.message_root,
+                found: seal.message_root,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    fn verify_block_body(
+        &self, header: &Header, parent: &Header, transactions: &[SignedTransaction],
+        receipts: &[TypedReceipt],
+    ) -> Result<(), M::Error> {
+        // the legacy seal carries no queue index, the difficulty counting it is not verified
+        if self.layout == SealLayout::Legacy {
+            return Ok(());
+        }
+        verify_l2_seal_messages(header, parent, transactions, receipts, L2_CROSS_LAYER_WITNESS)
+            .map_err(Into::into)
+    }
+
     fn open_block_header_timestamp(&self, parent_timestamp: u64) -> u64 {
         max(parent_timestamp, self.timestamp)
     }
@@ -42,3 +264,102 @@ impl<M: Machine> Engine<M> for L2Seal<M> {
         header_timestamp >= parent_timestamp
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{L2Seal, SealLayout};
+    use crate::engines::Engine;
+    use crate::spec::Spec;
+    use ethereum_types::H256;
+    use types::header::{Header, L2SealFields};
+    use types::l2_cfg::L2_BLOCK_MAX_GAS_LIMIT;
+
+    fn sealed_header(number: u64, timestamp: u64, seal: &L2SealFields) -> Header {
+        let mut header = Header::default();
+        header.set_number(number);
+        header.set_timestamp(timestamp);
+        header.set_gas_limit(L2_BLOCK_MAX_GAS_LIMIT.into());
+        header.set_seal(seal.seal_fields());
+        header
+    }
+
+    #[test]
+    fn l2_seal_verifies_basic() {
+        let engine = L2Seal::new(0, Spec::new_test_machine());
+        let header = sealed_header(1, 0, &L2SealFields::default());
+        assert!(engine.verify_block_basic(&header).is_ok());
+
+        let mut header = header;
+        header.set_seal(vec![]);
+        assert!(engine.verify_block_basic(&header).is_err());
+
+        let mut header = sealed_header(1, 0, &L2SealFields::default());
+        header.set_gas_limit(1.into());
+        assert!(engine.verify_block_basic(&header).is_err());
+    }
+
+    #[test]
+    fn l2_seal_verifies_family() {
+        let engine = L2Seal::new(0, Spec::new_test_machine());
+        let parent_seal =
+            L2SealFields { message_index: 3, message_root: H256::repeat_byte(1), queue_index: 5 };
+        let parent = sealed_header(1, 10, &parent_seal);
+
+        let mut header = sealed_header(2, 10, &parent_seal);
+        header.set_parent_hash(parent.hash());
+        assert!(engine.verify_block_family(&header, &parent).is_ok());
+
+        let mut header = sealed_header(2, 9, &parent_seal);
+        header.set_parent_hash(parent.hash());
+        assert!(engine.verify_block_family(&header, &parent).is_err());
+
+        let seal = L2SealFields { queue_index: 4, ..parent_seal.clone() };
+        let mut header = sealed_header(2, 10, &seal);
+        header.set_parent_hash(parent.hash());
+        assert!(engine.verify_block_family(&header, &parent).is_err());
+
+        let seal = L2SealFields { message_root: H256::repeat_byte(2), ..parent_seal.clone() };
+        let mut header = sealed_header(2, 10, &seal);
+        header.set_parent_hash(parent.hash());
+        assert!(engine.verify_block_family(&header, &parent).is_err());
+    }
+
+    #[test]
+    fn l2_seal_legacy_layout() {
+        let mut engine = L2Seal::new(0, Spec::new_test_machine());
+        engine.set_seal_layout(SealLayout::Legacy);
+        let seal =
+            L2SealFields { message_index: 3, message_root: H256::repeat_byte(1), queue_index: 0 };
+        let mut header = sealed_header(1, 0, &seal);
+        assert_eq!(engine.seal_fields(&header), L2SealFields::LEGACY_FIELDS);
+        assert!(engine.enqueued_difficulty());
+        assert!(engine.verify_block_basic(&header).is_err());
+
+        header.set_seal(seal.legacy_seal_fields());
+        assert!(engine.verify_block_basic(&header).is_ok());
+
+        let engine = L2Seal::new(0, Spec::new_test_machine());
+        assert!(!engine.enqueued_difficulty());
+        assert!(engine.verify_block_basic(&header).is_err());
+    }
+
+    #[test]
+    fn l2_seal_verifies_body() {
+        let engine = L2Seal::new(0, Spec::new_test_machine());
+        let parent_seal =
+            L2SealFields { message_index: 3, message_root: H256::repeat_byte(1), queue_index: 5 };
+        let parent = sealed_header(1, 10, &parent_seal);
+
+        let header = sealed_header(2, 10, &parent_seal);
+        assert!(engine.verify_block_body(&header, &parent, &[], &[]).is_ok());
+
+        // a block without messages nor enqueued transactions can't move the counters
+        let seal = L2SealFields { message_index: 4, ..parent_seal.clone() };
+        let header = sealed_header(2, 10, &seal);
+        assert!(engine.verify_block_body(&header, &parent, &[], &[]).is_err());
+
+        let seal = L2SealFields { queue_index: 6, ..parent_seal.clone() };
+        let header = sealed_header(2, 10, &seal);
+        assert!(engine.verify_block_body(&header, &parent, &[], &[]).is_err());
+    }
+}
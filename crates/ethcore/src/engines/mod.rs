@@ -23,7 +23,7 @@ mod null_engine;
 pub mod block_reward;
 
 pub use self::instant_seal::{InstantSeal, InstantSealParams};
-pub use self::l2_engine::L2Seal;
+pub use self::l2_engine::{compute_l2_seal, l2_messages, l2_messages_root, message_sent_event_id, verify_l2_seal_messages, L2Seal, SealLayout};
 pub use self::null_engine::NullEngine;
 
 pub use types::engines::ForkChoice;
@@ -39,6 +39,7 @@ use crate::error::Error;
 use crate::spec::CommonParams;
 use builtin::Builtin;
 use types::header::{ExtendedHeader, Header};
+use types::receipt::TypedReceipt;
 use types::transaction::{self, SignedTransaction, UnverifiedTransaction};
 use types::BlockNumber;
 use vm::{CreateContractAddress, EnvInfo, Schedule};
@@ -217,6 +218,11 @@ pub trait Engine<M: Machine>: Sync + Send {
         0
     }
 
+    /// Whether the header difficulty counts the enqueued transactions of the block.
+    fn enqueued_difficulty(&self) -> bool {
+        true
+    }
+
     /// Additional engine-specific information for the user/developer concerning `header`.
     fn extra_info(&self, _header: &Header) -> BTreeMap<String, String> {
         BTreeMap::new()
@@ -279,6 +285,14 @@ pub trait Engine<M: Machine>: Sync + Send {
         Ok(())
     }
 
+    /// Phase 4 verification. Check the header against the executed block body. Returns either a null `Ok` or a general error detailing the problem with import.
+    fn verify_block_body(
+        &self, _header: &Header, _parent: &Header, _transactions: &[SignedTransaction],
+        _receipts: &[TypedReceipt],
+    ) -> Result<(), M::Error> {
+        Ok(())
+    }
+
     /// Populate a header's fields based on its parent's header.
     /// Usually implements the chain scoring rule based on weight.
     fn populate_from_parent(&self, _header: &mut Header, _parent: &Header) {}
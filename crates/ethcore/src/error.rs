@@ -103,6 +103,10 @@ pub enum BlockError {
     RidiculousNumber(OutOfBounds<BlockNumber>),
     /// Timestamp header overflowed
     TimestampOverflow,
+    /// L2 seal queue index does not match the enqueued transactions of the block.
+    InvalidQueueIndex(Mismatch<u64>),
+    /// L2 seal message index does not match the cross layer messages of the block.
+    InvalidMessageIndex(Mismatch<u64>),
     /// Too many transactions from a particular address.
     TooManyTransactions(Address),
     /// Parent given is unknown.
@@ -162,6 +166,8 @@ impl fmt::Display for BlockError {
                 format!("Unknown transition to epoch number: {}", num)
             }
             TimestampOverflow => format!("Timestamp overflow"),
+            InvalidQueueIndex(ref mis) => format!("Invalid queue index in seal: {}", mis),
+            InvalidMessageIndex(ref mis) => format!("Invalid message index in seal: {}", mis),
             TooManyTransactions(ref address) => format!("Too many transactions from: {}", address),
         };
 
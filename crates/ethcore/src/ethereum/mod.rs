@@ -223,6 +223,24 @@ pub fn new_london_test() -> Spec {
     load(include_bytes!("../../res/chainspec/test/london_test.json"))
 }
 
+#[cfg(feature = "std")]
+/// Create a new Foundation Shanghai era spec.
+pub fn new_shanghai_test() -> Spec {
+    load(include_bytes!("../../res/chainspec/test/shanghai_test.json"))
+}
+
+#[cfg(feature = "std")]
+/// Create a new Foundation Cancun era spec.
+pub fn new_cancun_test() -> Spec {
+    load(include_bytes!("../../res/chainspec/test/cancun_test.json"))
+}
+
+#[cfg(feature = "std")]
+/// Create a new Foundation Prague era spec.
+pub fn new_prague_test() -> Spec {
+    load(include_bytes!("../../res/chainspec/test/prague_test.json"))
+}
+
 #[cfg(feature = "std")]
 /// Create a new BerlinToLondonAt5 era spec.
 pub fn new_berlin_to_london_test() -> Spec {
@@ -255,6 +273,24 @@ pub fn new_london_test_machine() -> EthereumMachine {
     load_machine(include_bytes!("../../res/chainspec/test/london_test.json"))
 }
 
+#[cfg(feature = "std")]
+/// Create a new Foundation Shanghai era chain spec.
+pub fn new_shanghai_test_machine() -> EthereumMachine {
+    load_machine(include_bytes!("../../res/chainspec/test/shanghai_test.json"))
+}
+
+#[cfg(feature = "std")]
+/// Create a new Foundation Cancun era chain spec.
+pub fn new_cancun_test_machine() -> EthereumMachine {
+    load_machine(include_bytes!("../../res/chainspec/test/cancun_test.json"))
+}
+
+#[cfg(feature = "std")]
+/// Create a new Foundation Prague era chain spec.
+pub fn new_prague_test_machine() -> EthereumMachine {
+    load_machine(include_bytes!("../../res/chainspec/test/prague_test.json"))
+}
+
 #[cfg(feature = "std")]
 /// Create a new Foundation Homestead-EIP210-era chain spec as though it never changed from Homestead/Frontier.
 pub fn new_eip210_test_machine() -> EthereumMachine {
@@ -55,6 +55,15 @@ pub struct Executed<T = FlatTrace, V = VMTrace> {
     /// where `tn` is current transaction.
     pub cumulative_gas_used: U256,
 
+    /// Fee paid for the used gas above the base fee.
+    pub priority_fee: U256,
+
+    /// Fee paid for the used gas at the base fee.
+    pub base_fee: U256,
+
+    /// L1 data fee paid by the sender on top of the gas cost.
+    pub l1_fee: U256,
+
     /// Vector of logs generated by transaction.
     pub logs: Vec<LogEntry>,
 
@@ -126,6 +135,8 @@ pub enum ExecutionError {
     SenderMustExist,
     /// Returned when transacting from an internal account.
     SenderMustEoa,
+    /// Returned when creating a contract from a sender missing from the deployer allowlist.
+    DeployerNotAllowed,
     /// Returned when internal evm error occurs.
     Internal(String),
     /// Returned when generic transaction occurs
@@ -190,6 +201,7 @@ impl fmt::Display for ExecutionError {
             MutableCallInStaticContext => "Mutable Call in static context".to_owned(),
             SenderMustExist => "Transacting from an empty account".to_owned(),
             SenderMustEoa => "Sender not an eoa".to_owned(),
+            DeployerNotAllowed => "Sender not an allowed deployer".to_owned(),
             Internal(ref msg) => msg.clone(),
             TransactionMalformed(ref err) => format!("Malformed transaction: {}", err),
         };
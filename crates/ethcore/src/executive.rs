@@ -20,7 +20,7 @@ use crate::executed::ExecutionError;
 pub use crate::executed::{Executed, ExecutionResult};
 use crate::externalities::*;
 use crate::factory::VmFactory;
-use crate::machine::EthereumMachine as Machine;
+use crate::machine::{is_allowed_deployer, l1_data_fee, EthereumMachine as Machine};
 use crate::state::{Backend as StateBackend, CleanupMode, State, Substate};
 use crate::trace::{self, Tracer, VMTracer};
 use crate::transaction_ext::Transaction;
@@ -28,16 +28,17 @@ use alloc::boxed::Box;
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
+use builtin::{EthereumBuiltin, MessagePasser};
 use bytes::{Bytes, BytesRef, ToPretty};
-use common_types::l2_cfg::INTRINSIC_GAS_FACTOR;
 use core::cmp;
 use core::convert::TryFrom;
 use ethereum_types::{Address, H256, U256, U512};
 use evm::{CallType, FinalizationResult, Finalize};
 use hash::{keccak, KECCAK_EMPTY};
-use types::transaction::{Action, SignedTransaction, TypedTransaction};
+use types::log_entry::LogEntry;
+use types::transaction::{Action, AuthorizationList, SignedTransaction, TypedTransaction};
 use vm::{
-    self, AccessList, ActionParams, ActionValue, CleanDustMode, CreateContractAddress, EnvInfo, ResumeCall, ResumeCreate, ReturnData, Schedule, TrapError
+    self, delegation, AccessList, ActionParams, ActionValue, CleanDustMode, CreateContractAddress, EnvInfo, ResumeCall, ResumeCreate, ReturnData, Schedule, TrapError
 };
 
 #[cfg(any(test, feature = "test-helpers"))]
@@ -344,6 +345,35 @@ impl<'a> CallCreateExecutive<'a> {
         Ok(())
     }
 
+    /// Append a message to the accumulator of the message passer builtin at `code_address` and
+    /// log it. Returns the index of the message as a 32 byte word.
+    fn pass_message<B: 'a + StateBackend>(
+        passer: &MessagePasser, params: &ActionParams, data: &[u8], static_flag: bool,
+        state: &mut State<B>, substate: &mut Substate, output: &mut BytesRef,
+    ) -> vm::Result<()> {
+        if static_flag {
+            return Err(vm::Error::MutableCallInStaticContext);
+        }
+
+        let address = params.code_address;
+        let next_index = state.storage_at(&address, &MessagePasser::index_key())?.to_low_u64_be();
+        let root = state.storage_at(&address, &MessagePasser::root_key())?;
+        let message =
+            passer.pass(data, params.sender, next_index, root).map_err(vm::Error::BuiltIn)?;
+
+        // a nonce keeps the account holding the accumulator from being cleared as empty
+        if !state.exists_and_not_null(&address)? {
+            state.inc_nonce(&address)?;
+        }
+        let next_index = H256::from_low_u64_be(message.index + 1);
+        state.set_storage(&address, MessagePasser::index_key(), next_index)?;
+        state.set_storage(&address, MessagePasser::root_key(), message.root)?;
+        substate.logs.push(LogEntry { address, topics: message.topics(), data: message.data() });
+        output.write(0, H256::from_low_u64_be(message.index).as_bytes());
+
+        Ok(())
+    }
+
     fn transfer_exec_balance<B: 'a + StateBackend>(
         params: &ActionParams, schedule: &Schedule, state: &mut State<B>, substate: &mut Substate,
     ) -> vm::Result<()> {
@@ -388,9 +418,6 @@ impl<'a> CallCreateExecutive<'a> {
             | Err(vm::Error::MutableCallInStaticContext)
             | Err(vm::Error::OutOfBounds)
             | Err(vm::Error::Reverted)
-            | Err(vm::Error::SubStackUnderflow { .. })
-            | Err(vm::Error::OutOfSubStack { .. })
-            | Err(vm::Error::InvalidSubEntry)
             | Err(vm::Error::InvalidCode)
             | Ok(FinalizationResult { apply_state: false, .. }) => {
                 if let Some(addr) = UNPRUNABLE_PRECOMPILE_ADDRESS {
@@ -487,12 +514,25 @@ impl<'a> CallCreateExecutive<'a> {
                         let mut builtin_out_buffer = Vec::new();
                         let result = {
                             let mut builtin_output = BytesRef::Flexible(&mut builtin_out_buffer);
-                            builtin.execute(data, &mut builtin_output)
+                            match builtin.native {
+                                EthereumBuiltin::MessagePasser(ref passer) => Self::pass_message(
+                                    passer,
+                                    &params,
+                                    data,
+                                    self.static_flag,
+                                    state,
+                                    substate,
+                                    &mut builtin_output,
+                                ),
+                                _ => builtin
+                                    .execute(data, &mut builtin_output)
+                                    .map_err(vm::Error::BuiltIn),
+                            }
                         };
                         if let Err(e) = result {
                             state.revert_to_checkpoint();
 
-                            Err(vm::Error::BuiltIn(e))
+                            Err(e)
                         } else {
                             state.discard_checkpoint();
 
@@ -1009,8 +1049,11 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
     {
         let sender = t.sender();
         let balance = self.state.balance(&sender)?;
-        let needed_balance =
-            t.tx().value.saturating_add(t.tx().gas.saturating_mul(t.tx().gas_price));
+        let needed_balance = t
+            .tx()
+            .value
+            .saturating_add(t.tx().gas.saturating_mul(t.tx().gas_price))
+            .saturating_add(self.l1_fee(t)?);
         if balance < needed_balance {
             // give the sender a sufficient balance
             self.state.add_balance(&sender, &(needed_balance - balance), CleanupMode::NoEmpty)?;
@@ -1019,6 +1062,35 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         self.transact(t, options)
     }
 
+    /// L1 data fee paid by `t`, zero for enqueued transactions which are already on L1.
+    fn l1_fee(&self, t: &SignedTransaction) -> Result<U256, ExecutionError> {
+        let params = self.machine.params();
+        if self.info.number < params.l1_fee_transition || t.is_enqueued() {
+            return Ok(U256::zero());
+        }
+        Ok(l1_data_fee(&*self.state, &params.l1_fee_oracle, t.encode().len())?)
+    }
+
+    /// Whether `t` is an enqueued transaction, its gas bought and burnt and its value deposited
+    /// on L1 already.
+    fn is_prepaid(&self, t: &SignedTransaction) -> bool {
+        t.is_enqueued() && self.info.number >= self.machine.params().enqueued_tx_transition
+    }
+
+    /// Gas price paid on L2 by `t`, zero for prepaid transactions.
+    fn gas_price(&self, t: &SignedTransaction) -> U256 {
+        if self.is_prepaid(t) {
+            U256::zero()
+        } else {
+            t.effective_gas_price(self.info.base_fee)
+        }
+    }
+
+    /// Whether transaction fees are left to the block to distribute when it closes.
+    fn defers_fees(&self) -> bool {
+        self.info.number >= self.machine.params().fee_distribution_transition
+    }
+
     /// Execute transaction/call with tracing enabled
     fn transact_with_tracer<T, V>(
         &'a mut self, t: &SignedTransaction, check_nonce: bool, output_from_create: bool,
@@ -1046,6 +1118,13 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
                     ));
                 }
             }
+            TypedTransaction::EIP7702Transaction(_) => {
+                if !schedule.eip7702 {
+                    return Err(ExecutionError::TransactionMalformed(
+                        "7702 type of transactions not enabled".into(),
+                    ));
+                }
+            }
             TypedTransaction::Legacy(_) => (), //legacy transactions are allways valid
         };
 
@@ -1058,7 +1137,9 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
             let code_hash = self.state.code_hash(&sender).expect(info.as_str());
             match code_hash {
                 Some(hash) => {
-                    if hash != KECCAK_EMPTY && hash != H256::zero() {
+                    // accounts delegated by EIP-7702 keep sending transactions
+                    let delegated = schedule.eip7702 && self.state.delegation(&sender)?.is_some();
+                    if hash != KECCAK_EMPTY && hash != H256::zero() && !delegated {
                         return Err(ExecutionError::SenderMustEoa);
                     }
                 }
@@ -1066,22 +1147,40 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
             };
         }
 
+        // ensure init code is within limits, https://eips.ethereum.org/EIPS/eip-3860
+        if schedule.eip3860
+            && t.tx().action == Action::Create
+            && t.tx().data.len() > schedule.max_initcode_size
+        {
+            return Err(ExecutionError::TransactionMalformed(
+                "Init code size exceeds EIP-3860 limit".into(),
+            ));
+        }
+
+        // ensure the sender may deploy contracts while deployments are permissioned
+        if t.tx().action == Action::Create {
+            if let Some(allowlist) = self.machine.deployer_allowlist(self.info.number) {
+                if !is_allowed_deployer(&*self.state, &allowlist, &sender)? {
+                    return Err(ExecutionError::DeployerNotAllowed);
+                }
+            }
+        }
+
         let mut base_gas_required = U256::from(t.tx().gas_required(&schedule));
 
         let mut access_list = AccessList::new(schedule.eip2929);
 
         if schedule.eip2929 {
             access_list.insert_address(sender);
+            if schedule.eip3651 {
+                access_list.insert_address(self.info.author);
+            }
             for (address, builtin) in self.machine.builtins() {
                 if builtin.is_active(self.info.number) {
                     access_list.insert_address(*address);
                 }
             }
-            let mut intrinsic_gas_factor: usize = INTRINSIC_GAS_FACTOR;
-            #[cfg(any(test, feature = "test-helpers"))]
-            {
-                intrinsic_gas_factor = 1;
-            }
+            let intrinsic_gas_factor = schedule.tx_data_gas_factor;
             if let Some(al) = t.access_list() {
                 for item in al.iter() {
                     access_list.insert_address(item.0);
@@ -1098,6 +1197,11 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
             }
         }
 
+        if let Some(authorization_list) = t.authorization_list() {
+            base_gas_required +=
+                (vm::schedule::EIP7702_PER_EMPTY_ACCOUNT_COST * authorization_list.len()).into();
+        }
+
         if t.is_enqueued() {
             base_gas_required = U256::zero();
         }
@@ -1135,7 +1239,10 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         }
 
         // ensure that the user was willing to at least pay the base fee
-        if t.tx().gas_price < self.info.base_fee.unwrap_or_default() && !t.has_zero_gas_price() {
+        if t.tx().gas_price < self.info.base_fee.unwrap_or_default()
+            && !t.has_zero_gas_price()
+            && !self.is_prepaid(t)
+        {
             return Err(ExecutionError::GasPriceLowerThanBaseFee {
                 gas_price: t.tx().gas_price,
                 base_fee: self.info.base_fee.unwrap_or_default(),
@@ -1151,9 +1258,13 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 
         // TODO: we might need bigints here, or at least check overflows.
         let balance = self.state.balance(&sender)?;
-        let gas_cost_effective = t.tx().gas.full_mul(t.effective_gas_price(self.info.base_fee));
-        let gas_cost_max = t.tx().gas.full_mul(t.tx().gas_price);
-        let needed_balance = U512::from(t.tx().value) + gas_cost_max;
+        let gas_cost_effective = t.tx().gas.full_mul(self.gas_price(t));
+        let gas_cost_max =
+            if self.is_prepaid(t) { U512::zero() } else { t.tx().gas.full_mul(t.tx().gas_price) };
+        let l1_fee = self.l1_fee(t)?;
+        // the value of a prepaid transaction is a deposit locked on L1, minted to its sender
+        let deposit = if self.is_prepaid(t) { t.tx().value } else { U256::zero() };
+        let needed_balance = U512::from(t.tx().value - deposit) + gas_cost_max + U512::from(l1_fee);
 
         // avoid unaffordable transactions
         let balance512 = U512::from(balance);
@@ -1170,11 +1281,32 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         if !schedule.keep_unsigned_nonce || !t.is_unsigned() {
             self.state.inc_nonce(&sender)?;
         }
+        // the deposit is kept even if the transaction reverts
+        if !deposit.is_zero() {
+            self.state.add_balance(&sender, &deposit, substate.to_cleanup_mode(&schedule))?;
+        }
         self.state.sub_balance(
             &sender,
             &U256::try_from(gas_cost_effective).expect("Total cost (value + gas_cost_effective) is lower than max allowed balance (U256); gas_cost has to fit U256; qed"),
             &mut substate.to_cleanup_mode(&schedule),
         )?;
+        if !l1_fee.is_zero() {
+            self.state.sub_balance(&sender, &l1_fee, &mut substate.to_cleanup_mode(&schedule))?;
+        }
+        if !l1_fee.is_zero() && !self.defers_fees() {
+            self.state.add_balance(
+                &self.machine.params().l1_fee_vault,
+                &l1_fee,
+                substate.to_cleanup_mode(&schedule),
+            )?;
+        }
+        // transient storage and the EIP-6780 created set live for one transaction only
+        self.state.begin_transaction();
+
+        // delegations are kept even if the transaction reverts
+        if let Some(authorization_list) = t.authorization_list() {
+            self.apply_authorizations(authorization_list, &mut access_list, &mut substate)?;
+        }
 
         let (result, output) = match t.tx().action {
             Action::Create => {
@@ -1192,7 +1324,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
                     sender: sender.clone(),
                     origin: sender.clone(),
                     gas: init_gas,
-                    gas_price: t.effective_gas_price(self.info.base_fee),
+                    gas_price: self.gas_price(t),
                     value: ActionValue::Transfer(t.tx().value),
                     code: Some(Arc::new(t.tx().data.clone())),
                     data: None,
@@ -1209,16 +1341,24 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
             }
             Action::Call(ref address) => {
                 access_list.insert_address(address.clone());
+                let (code, code_hash) = if schedule.eip7702 {
+                    if let Some(target) = self.state.delegation(address)? {
+                        access_list.insert_address(target);
+                    }
+                    self.state.delegated_code(address)?
+                } else {
+                    (self.state.code(address)?, self.state.code_hash(address)?)
+                };
                 let params = ActionParams {
                     code_address: address.clone(),
                     address: address.clone(),
                     sender: sender.clone(),
                     origin: sender.clone(),
                     gas: init_gas,
-                    gas_price: t.effective_gas_price(self.info.base_fee),
+                    gas_price: self.gas_price(t),
                     value: ActionValue::Transfer(t.tx().value),
-                    code: self.state.code(address)?,
-                    code_hash: self.state.code_hash(address)?,
+                    code,
+                    code_hash,
                     data: Some(t.tx().data.clone()),
                     call_type: CallType::Call,
                     params_type: vm::ParamsType::Separate,
@@ -1234,7 +1374,53 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         };
 
         // finalize here!
-        Ok(self.finalize(t, substate, result, output, tracer.drain(), vm_tracer.drain())?)
+        Ok(self.finalize(t, substate, result, output, l1_fee, tracer.drain(), vm_tracer.drain())?)
+    }
+
+    /// Applies the authorizations of an EIP-7702 transaction in order.
+    /// An invalid authorization is skipped, it does not invalidate the transaction.
+    fn apply_authorizations(
+        &mut self, authorization_list: &AuthorizationList, access_list: &mut AccessList,
+        substate: &mut Substate,
+    ) -> Result<(), ExecutionError> {
+        let chain_id = U256::from(self.machine.params().chain_id);
+        for authorization in authorization_list.iter() {
+            if !authorization.chain_id.is_zero() && authorization.chain_id != chain_id {
+                continue;
+            }
+            if authorization.nonce == u64::max_value() {
+                continue;
+            }
+            let authority = match authorization.authority() {
+                Some(authority) => authority,
+                None => continue,
+            };
+            access_list.insert_address(authority);
+
+            // only accounts without code or already delegated can be (re)delegated
+            let has_code = self.state.code_size(&authority)?.map_or(false, |size| size > 0);
+            if has_code && self.state.delegation(&authority)?.is_none() {
+                continue;
+            }
+            if self.state.nonce(&authority)? != authorization.nonce.into() {
+                continue;
+            }
+
+            if self.state.exists(&authority)? {
+                substate.sstore_clears_refund += (vm::schedule::EIP7702_PER_EMPTY_ACCOUNT_COST
+                    - vm::schedule::EIP7702_PER_AUTH_BASE_COST)
+                    as i128;
+            }
+            // delegating to the zero address clears the delegation
+            let code = if authorization.address.is_zero() {
+                Vec::new()
+            } else {
+                delegation::designator(&authorization.address)
+            };
+            self.state.reset_code(&authority, code)?;
+            self.state.inc_nonce(&authority)?;
+        }
+        Ok(())
     }
 
     /// Calls contract function with given contract params and stack depth.
@@ -1360,7 +1546,8 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
     /// Finalizes the transaction (does refunds and suicides).
     fn finalize<T, V>(
         &mut self, t: &SignedTransaction, mut substate: Substate,
-        result: vm::Result<FinalizationResult>, output: Bytes, trace: Vec<T>, vm_trace: Option<V>,
+        result: vm::Result<FinalizationResult>, output: Bytes, l1_fee: U256, trace: Vec<T>,
+        vm_trace: Option<V>,
     ) -> Result<Executed<T, V>, ExecutionError> {
         let schedule = self.schedule;
 
@@ -1390,10 +1577,8 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         let gas_left = gas_left_prerefund + refunded;
 
         let gas_used = t.tx().gas.saturating_sub(gas_left);
-        let (refund_value, overflow_1) =
-            gas_left.overflowing_mul(t.effective_gas_price(self.info.base_fee));
-        let (fees_value, overflow_2) =
-            gas_used.overflowing_mul(t.effective_gas_price(self.info.base_fee));
+        let (refund_value, overflow_1) = gas_left.overflowing_mul(self.gas_price(t));
+        let (fees_value, overflow_2) = gas_used.overflowing_mul(self.gas_price(t));
         if overflow_1 || overflow_2 {
             return Err(ExecutionError::TransactionMalformed("U256 Overflow".to_string()));
         }
@@ -1401,7 +1586,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         // Up until now, fees_value is calculated for each type of transaction based on their gas prices
         // Now, if eip1559 is activated, burn the base fee
         // miner only receives the inclusion fee; note that the base fee is not given to anyone (it is burned)
-        let burnt_fee = if schedule.eip1559 && !t.has_zero_gas_price() {
+        let burnt_fee = if schedule.eip1559 && !t.has_zero_gas_price() && !self.is_prepaid(t) {
             let (fee, overflow_3) =
                 gas_used.overflowing_mul(self.info.base_fee.unwrap_or_default());
             if overflow_3 {
@@ -1421,13 +1606,16 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
         // Below: NoEmpty is safe since the sender must already be non-null to have sent this transaction
         self.state.add_balance(&sender, &refund_value, CleanupMode::NoEmpty)?;
         //trace!( "exec::finalize: Compensating author: fees_value={}, author={}\n", fees_value, &self.info.author );
-        self.state.add_balance(
-            &self.info.author,
-            &fees_value,
-            substate.to_cleanup_mode(&schedule),
-        )?;
+        if !self.defers_fees() {
+            self.state.add_balance(
+                &self.info.author,
+                &fees_value,
+                substate.to_cleanup_mode(&schedule),
+            )?;
+        }
 
         if burnt_fee > U256::from(0u32)
+            && !self.defers_fees()
             && self.machine.params().eip1559_fee_collector.is_some()
             && self.info.number >= self.machine.params().eip1559_fee_collector_transition
         {
@@ -1468,6 +1656,9 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
                 gas_used: t.tx().gas,
                 refunded: U256::zero(),
                 cumulative_gas_used: self.info.gas_used + t.tx().gas,
+                priority_fee: fees_value,
+                base_fee: burnt_fee,
+                l1_fee,
                 logs: vec![],
                 contracts_created: vec![],
                 output: output,
@@ -1481,6 +1672,9 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
                 gas_used: gas_used,
                 refunded: refunded,
                 cumulative_gas_used: self.info.gas_used + gas_used,
+                priority_fee: fees_value,
+                base_fee: burnt_fee,
+                l1_fee,
                 logs: substate.logs,
                 contracts_created: substate.contracts_created,
                 output: output,
@@ -1505,6 +1699,7 @@ mod tests {
         trace, ExecutiveTracer, ExecutiveVMTracer, FlatTrace, MemoryDiff, NoopTracer, NoopVMTracer, StorageDiff, Tracer, VMExecutedOperation, VMOperation, VMTrace, VMTracer
     };
     use alloc::sync::Arc;
+    use builtin::{Builtin, Linear, Pricing};
     use bytes::Bytes;
     use core::str::FromStr;
     use crypto::publickey::{Generator, Random};
@@ -1512,7 +1707,7 @@ mod tests {
     use evm::{evm_test, evm_test_ignore, Factory, VMType};
     use hex::FromHex;
     use types::transaction::{
-        AccessListTx, Action, EIP1559TransactionTx, Transaction, TypedTransaction
+        AccessListTx, Action, Authorization, AuthorizationList, EIP1559TransactionTx, EIP7702TransactionTx, Transaction, TypedTransaction
     };
     use vm::{ActionParams, ActionValue, CallType, CreateContractAddress, EnvInfo};
 
@@ -1534,6 +1729,18 @@ mod tests {
         machine
     }
 
+    fn make_shanghai_machine(max_depth: usize) -> EthereumMachine {
+        let mut machine = ethereum::new_shanghai_test_machine();
+        machine.set_schedule_creation_rules(Box::new(move |s, _| s.max_depth = max_depth));
+        machine
+    }
+
+    fn make_prague_machine(max_depth: usize) -> EthereumMachine {
+        let mut machine = ethereum::new_prague_test_machine();
+        machine.set_schedule_creation_rules(Box::new(move |s, _| s.max_depth = max_depth));
+        machine
+    }
+
     #[test]
     fn test_contract_address() {
         let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
@@ -2424,6 +2631,172 @@ mod tests {
         );
     }
 
+    evm_test! {test_transact_l1_fee: test_transact_l1_fee_int}
+    fn test_transact_l1_fee(factory: Factory) {
+        let keypair = Random.generate();
+        let t = TypedTransaction::Legacy(Transaction {
+            action: Action::Call(Address::from_low_u64_be(10)),
+            value: U256::from(17),
+            data: vec![],
+            gas: U256::from(100_000),
+            gas_price: U256::zero(),
+            nonce: U256::zero(),
+        })
+        .sign(keypair.secret(), None);
+        let sender = t.sender();
+        let l1_fee = U256::from(t.encode().len() * 16 * 1_000);
+
+        let mut params = ethereum::new_london_test_machine().params().clone();
+        params.l1_fee_transition = 0;
+        let oracle = params.l1_fee_oracle;
+        let vault = params.l1_fee_vault;
+        let machine = EthereumMachine::regular(params, Default::default());
+        let mut state = get_temp_state_with_factory(factory);
+        let base_fee = H256::from_low_u64_be(1_000);
+        state.set_storage(&oracle, H256::from_low_u64_be(0), base_fee).unwrap();
+        let scalar = H256::from_low_u64_be(1_000_000);
+        state.set_storage(&oracle, H256::from_low_u64_be(1), scalar).unwrap();
+        state.add_balance(&sender, &(l1_fee + 16), CleanupMode::NoEmpty).unwrap();
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(100_000);
+        let schedule = machine.schedule(info.number);
+
+        // the fee is part of the upfront cost
+        let res = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.transact(&t, TransactOptions::with_no_tracing())
+        };
+        match res {
+            Err(ExecutionError::NotEnoughCash { required, .. }) => {
+                assert_eq!(required, U512::from(l1_fee + 17))
+            }
+            _ => assert!(false, "Expected not enough cash error. {:?}", res),
+        }
+
+        state.add_balance(&sender, &U256::one(), CleanupMode::NoEmpty).unwrap();
+        let executed = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+        };
+        assert_eq!(executed.l1_fee, l1_fee);
+        assert_eq!(state.balance(&sender).unwrap(), U256::zero());
+        assert_eq!(state.balance(&vault).unwrap(), l1_fee);
+    }
+
+    evm_test! {test_transact_defers_fees: test_transact_defers_fees_int}
+    fn test_transact_defers_fees(factory: Factory) {
+        let keypair = Random.generate();
+        let t = TypedTransaction::Legacy(Transaction {
+            action: Action::Call(Address::from_low_u64_be(10)),
+            value: U256::zero(),
+            data: vec![],
+            gas: U256::from(21_000),
+            gas_price: U256::from(3),
+            nonce: U256::zero(),
+        })
+        .sign(keypair.secret(), None);
+        let sender = t.sender();
+
+        let mut params = ethereum::new_london_test_machine().params().clone();
+        params.fee_distribution_transition = 0;
+        let machine = EthereumMachine::regular(params, Default::default());
+        let mut state = get_temp_state_with_factory(factory);
+        state.add_balance(&sender, &U256::from(63_000), CleanupMode::NoEmpty).unwrap();
+        let mut info = EnvInfo::default();
+        info.author = Address::from_low_u64_be(0xa);
+        info.gas_limit = U256::from(100_000);
+        info.base_fee = Some(U256::from(2));
+        let schedule = machine.schedule(info.number);
+
+        let executed = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+        };
+        // the fees are left to the block, the author is not paid yet
+        assert_eq!(executed.priority_fee, U256::from(21_000));
+        assert_eq!(executed.base_fee, U256::from(42_000));
+        assert_eq!(state.balance(&sender).unwrap(), U256::zero());
+        assert_eq!(state.balance(&info.author).unwrap(), U256::zero());
+    }
+
+    evm_test! {test_transact_prepaid_enqueued: test_transact_prepaid_enqueued_int}
+    fn test_transact_prepaid_enqueued(factory: Factory) {
+        let keypair = Random.generate();
+        let t = TypedTransaction::Legacy(Transaction {
+            action: Action::Call(Address::from_low_u64_be(10)),
+            value: U256::from(17),
+            data: vec![],
+            gas: U256::from(100_000),
+            gas_price: U256::one(),
+            nonce: U256::from(types::l2_cfg::INITIAL_ENQUEUE_TX_NONCE),
+        })
+        .sign(keypair.secret(), None);
+        let sender = t.sender();
+
+        let mut params = ethereum::new_london_test_machine().params().clone();
+        params.enqueued_tx_transition = 0;
+        let machine = EthereumMachine::regular(params, Default::default());
+        let mut state = get_temp_state_with_factory(factory);
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(100_000);
+        info.base_fee = Some(U256::from(7));
+        let schedule = machine.schedule(info.number);
+
+        // gas bought on L1 is neither charged, checked against the base fee nor refunded
+        let executed = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+        };
+        assert_eq!(executed.gas_used, U256::zero());
+        assert_eq!(executed.priority_fee, U256::zero());
+        assert_eq!(executed.base_fee, U256::zero());
+        assert_eq!(state.balance(&sender).unwrap(), U256::zero());
+        assert_eq!(state.balance(&info.author).unwrap(), U256::zero());
+        // the deposited value is minted to the sender and sent along
+        assert_eq!(state.balance(&Address::from_low_u64_be(10)).unwrap(), U256::from(17));
+    }
+
+    evm_test! {test_transact_deployer_allowlist: test_transact_deployer_allowlist_int}
+    fn test_transact_deployer_allowlist(factory: Factory) {
+        let keypair = Random.generate();
+        let t = TypedTransaction::Legacy(Transaction {
+            action: Action::Create,
+            value: U256::zero(),
+            data: FromHex::from_hex("3331600055").unwrap(),
+            gas: U256::from(100_000),
+            gas_price: U256::zero(),
+            nonce: U256::zero(),
+        })
+        .sign(keypair.secret(), None);
+        let sender = t.sender();
+
+        let mut params = ethereum::new_london_test_machine().params().clone();
+        params.deployer_allowlist_transition = 0;
+        let allowlist = params.deployer_allowlist_contract_address;
+        let machine = EthereumMachine::regular(params, Default::default());
+        let mut state = get_temp_state_with_factory(factory);
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(100_000);
+        let schedule = machine.schedule(info.number);
+
+        let res = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.transact(&t, TransactOptions::with_no_tracing())
+        };
+        match res {
+            Err(ExecutionError::DeployerNotAllowed) => (),
+            _ => assert!(false, "Expected deployer not allowed error. {:?}", res),
+        }
+
+        let key = crate::machine::deployer_allowlist_key(&sender);
+        state.set_storage(&allowlist, key, H256::from_low_u64_be(1)).unwrap();
+        let executed = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+        };
+        assert!(executed.exception.is_none());
+    }
+
     evm_test! {test_transact_invalid_nonce: test_transact_invalid_nonce_int}
     fn test_transact_invalid_nonce(factory: Factory) {
         let keypair = Random.generate();
@@ -2583,6 +2956,208 @@ mod tests {
         }
     }
 
+    evm_test! {test_initcode_too_big: test_initcode_too_big_int}
+    fn test_initcode_too_big(factory: Factory) {
+        let keypair = Random.generate();
+        let t = TypedTransaction::Legacy(Transaction {
+            action: Action::Create,
+            value: U256::zero(),
+            data: vec![0; 49153],
+            gas: U256::from(1_000_000),
+            gas_price: U256::zero(),
+            nonce: U256::zero(),
+        })
+        .sign(keypair.secret(), None);
+
+        let mut state = get_temp_state_with_factory(factory);
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(1_000_000);
+        let machine = make_shanghai_machine(0);
+        let schedule = machine.schedule(info.number);
+
+        let res = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            let opts = TransactOptions::with_no_tracing();
+            ex.transact(&t, opts)
+        };
+
+        match res {
+            Err(ExecutionError::TransactionMalformed(_)) => (),
+            _ => assert!(false, "Expected malformed transaction error. {:?}", res),
+        }
+    }
+
+    fn eip7702_tx(to: Address, authorization_list: AuthorizationList) -> TypedTransaction {
+        TypedTransaction::EIP7702Transaction(EIP7702TransactionTx {
+            transaction: EIP1559TransactionTx {
+                transaction: AccessListTx::new(
+                    Transaction {
+                        action: Action::Call(to),
+                        value: U256::zero(),
+                        data: vec![],
+                        gas: U256::from(100_000),
+                        gas_price: U256::zero(),
+                        nonce: U256::zero(),
+                    },
+                    vec![],
+                ),
+                max_priority_fee_per_gas: U256::zero(),
+            },
+            authorization_list,
+        })
+    }
+
+    evm_test! {test_transact_eip7702: test_transact_eip7702_int}
+    fn test_transact_eip7702(factory: Factory) {
+        let keypair = Random.generate();
+        let authority = Random.generate();
+        let authority_address = crypto::publickey::public_to_address(authority.public());
+        // PUSH1 1 PUSH1 0 SSTORE
+        let delegate = Address::from_low_u64_be(0xc0de);
+        let authorization = Authorization::sign(authority.secret(), 0.into(), delegate, 0);
+        let t = eip7702_tx(authority_address, vec![authorization]).sign(keypair.secret(), None);
+
+        let mut state = get_temp_state_with_factory(factory);
+        state.init_code(&delegate, FromHex::from_hex("6001600055").unwrap()).unwrap();
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(100_000);
+        let machine = make_prague_machine(0);
+        let schedule = machine.schedule(info.number);
+
+        let res = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            let opts = TransactOptions::with_no_tracing();
+            ex.transact(&t, opts).unwrap()
+        };
+
+        assert!(res.exception.is_none());
+        assert_eq!(
+            state.code(&authority_address).unwrap().unwrap().to_vec(),
+            delegation::designator(&delegate)
+        );
+        assert_eq!(state.delegation(&authority_address).unwrap(), Some(delegate));
+        assert_eq!(state.nonce(&authority_address).unwrap(), U256::one());
+        // the delegate code ran on the storage of the authority
+        assert_eq!(
+            state.storage_at(&authority_address, &H256::zero()).unwrap(),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(state.storage_at(&delegate, &H256::zero()).unwrap(), H256::zero());
+    }
+
+    evm_test! {test_transact_eip7702_skips_invalid_authorization: test_transact_eip7702_skips_invalid_authorization_int}
+    fn test_transact_eip7702_skips_invalid_authorization(factory: Factory) {
+        let keypair = Random.generate();
+        let authority = Random.generate();
+        let authority_address = crypto::publickey::public_to_address(authority.public());
+        let delegate = Address::from_low_u64_be(0xc0de);
+        // authority nonce is zero
+        let authorization = Authorization::sign(authority.secret(), 0.into(), delegate, 1);
+        let t = eip7702_tx(authority_address, vec![authorization]).sign(keypair.secret(), None);
+
+        let mut state = get_temp_state_with_factory(factory);
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(100_000);
+        let machine = make_prague_machine(0);
+        let schedule = machine.schedule(info.number);
+
+        let res = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            let opts = TransactOptions::with_no_tracing();
+            ex.transact(&t, opts).unwrap()
+        };
+
+        assert!(res.exception.is_none());
+        assert_eq!(res.gas_used, U256::from(21_000 + 25_000));
+        assert_eq!(state.delegation(&authority_address).unwrap(), None);
+        assert_eq!(state.nonce(&authority_address).unwrap(), U256::zero());
+        assert_eq!(state.nonce(&t.sender()).unwrap(), U256::one());
+    }
+
+    #[test]
+    fn test_call_to_message_passer() {
+        let sender = Address::from_str("4444444444444444444444444444444444444444").unwrap();
+        let passer = Address::from_low_u64_be(0x221);
+        let builtin = Builtin {
+            pricer: vec![(0, Pricing::Linear(Linear { base: 45000, word: 300 }))]
+                .into_iter()
+                .collect(),
+            native: EthereumBuiltin::MessagePasser(MessagePasser),
+        };
+        let params = ethereum::new_prague_test_machine().params().clone();
+        let machine =
+            EthereumMachine::regular(params, vec![(passer, builtin)].into_iter().collect());
+
+        let mut data = vec![0x11u8; 20];
+        data.extend_from_slice(b"hello");
+        let mut params = ActionParams::default();
+        params.address = passer;
+        params.code_address = passer;
+        params.sender = sender;
+        params.origin = sender;
+        params.gas = U256::from(100_000);
+        params.data = Some(data.clone());
+        params.call_type = CallType::Call;
+        let mut state = get_temp_state();
+        let info = EnvInfo::default();
+        let schedule = machine.schedule(info.number);
+
+        let mut substate = Substate::new();
+        let FinalizationResult { gas_left, return_data, .. } = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.call(params.clone(), &mut substate, &mut NoopTracer, &mut NoopVMTracer).unwrap()
+        };
+        let first = MessagePasser.pass(&data, sender, 0, H256::zero()).unwrap();
+        assert_eq!(gas_left, U256::from(100_000 - 45_300));
+        assert_eq!(&*return_data, H256::zero().as_bytes());
+        assert_eq!(
+            substate.logs,
+            vec![LogEntry { address: passer, topics: first.topics(), data: first.data() }]
+        );
+
+        // the second message continues the accumulator
+        let mut substate = Substate::new();
+        let FinalizationResult { return_data, .. } = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            ex.call(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer).unwrap()
+        };
+        let second = MessagePasser.pass(&data, sender, 1, first.root).unwrap();
+        assert_eq!(&*return_data, H256::from_low_u64_be(1).as_bytes());
+        assert_eq!(substate.logs[0].topics, second.topics());
+        assert_eq!(
+            state.storage_at(&passer, &MessagePasser::index_key()).unwrap(),
+            H256::from_low_u64_be(2)
+        );
+        assert_eq!(state.storage_at(&passer, &MessagePasser::root_key()).unwrap(), second.root);
+    }
+
+    evm_test! {test_eip7702_not_enabled: test_eip7702_not_enabled_int}
+    fn test_eip7702_not_enabled(factory: Factory) {
+        let keypair = Random.generate();
+        let authority = Random.generate();
+        let authorization =
+            Authorization::sign(authority.secret(), 0.into(), Address::from_low_u64_be(0xc0de), 0);
+        let t = eip7702_tx(Address::from_low_u64_be(10), vec![authorization])
+            .sign(keypair.secret(), None);
+
+        let mut state = get_temp_state_with_factory(factory);
+        let mut info = EnvInfo::default();
+        info.gas_limit = U256::from(100_000);
+        let machine = make_shanghai_machine(0);
+        let schedule = machine.schedule(info.number);
+
+        let res = {
+            let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+            let opts = TransactOptions::with_no_tracing();
+            ex.transact(&t, opts)
+        };
+
+        match res {
+            Err(ExecutionError::TransactionMalformed(_)) => (),
+            _ => assert!(false, "Expected malformed transaction error. {:?}", res),
+        }
+    }
+
     evm_test! {test_too_big_max_priority_fee_with_not_enough_cash: test_too_big_max_priority_fee_with_not_enough_cash_int}
     fn test_too_big_max_priority_fee_with_not_enough_cash(factory: Factory) {
         let keypair = Random.generate();
@@ -16,7 +16,7 @@
 
 //! Transaction Execution environment.
 use crate::executive::{into_message_call_result, *};
-use crate::machine::EthereumMachine as Machine;
+use crate::machine::{history_storage_key, is_allowed_deployer, EthereumMachine as Machine};
 use crate::state::{Backend as StateBackend, CleanupMode, State, Substate};
 use crate::trace::{Tracer, VMTracer};
 use alloc::sync::Arc;
@@ -136,6 +136,19 @@ where
         }
     }
 
+    fn transient_storage_at(&self, key: &H256) -> vm::Result<H256> {
+        Ok(self.state.transient_storage_at(&self.origin_info.address, key))
+    }
+
+    fn set_transient_storage(&mut self, key: H256, value: H256) -> vm::Result<()> {
+        if self.static_flag {
+            Err(vm::Error::MutableCallInStaticContext)
+        } else {
+            self.state.set_transient_storage(&self.origin_info.address, key, value);
+            Ok(())
+        }
+    }
+
     fn is_static(&self) -> bool {
         return self.static_flag;
     }
@@ -160,6 +173,13 @@ where
         match *number < U256::from(self.env_info.number)
             && number.low_u64() >= cmp::max(256, self.env_info.number) - 256
         {
+            true if self.schedule.eip2935 => {
+                let address = self.machine.params().eip2935_contract_address;
+                let key = history_storage_key(number.low_u64());
+                self.state
+                    .storage_at(&address, &key)
+                    .expect("Fatal error while reading block hash history")
+            }
             true => {
                 let index = self.env_info.number - number.low_u64() - 1;
                 assert!(
@@ -177,6 +197,14 @@ where
         &mut self, gas: &U256, value: &U256, code: &[u8], address_scheme: CreateContractAddress,
         trap: bool,
     ) -> ::core::result::Result<ContractCreateResult, TrapKind> {
+        // deployments are permissioned by the origin of the transaction
+        if let Some(allowlist) = self.machine.deployer_allowlist(self.env_info.number) {
+            match is_allowed_deployer(self.state, &allowlist, &self.origin_info.origin) {
+                Ok(true) => {}
+                _ => return Ok(ContractCreateResult::Failed),
+            }
+        }
+
         // create new contract address
         let (address, code_hash) = match self.state.nonce(&self.origin_info.address) {
             Ok(nonce) => contract_address(address_scheme, &self.origin_info.address, &nonce, &code),
@@ -246,10 +274,13 @@ where
         &mut self, gas: &U256, sender_address: &Address, receive_address: &Address,
         value: Option<U256>, data: &[u8], code_address: &Address, call_type: CallType, trap: bool,
     ) -> ::core::result::Result<MessageCallResult, TrapKind> {
-        let code_res = self
-            .state
-            .code(code_address)
-            .and_then(|code| self.state.code_hash(code_address).map(|hash| (code, hash)));
+        let code_res = if self.schedule.eip7702 {
+            self.state.delegated_code(code_address)
+        } else {
+            self.state
+                .code(code_address)
+                .and_then(|code| self.state.code_hash(code_address).map(|hash| (code, hash)))
+        };
 
         let (code, code_hash) = match code_res {
             Ok((code, hash)) => (code, hash),
@@ -298,6 +329,7 @@ where
         Ok(into_message_call_result(out))
     }
 
+    // EXTCODE* do not follow EIP-7702 delegations, they see the designator itself
     fn extcode(&self, address: &Address) -> vm::Result<Option<Arc<Bytes>>> {
         Ok(self.state.code(address)?)
     }
@@ -314,6 +346,14 @@ where
         Ok(self.state.code_size(address)?)
     }
 
+    fn delegation(&self, address: &Address) -> vm::Result<Option<Address>> {
+        if self.schedule.eip7702 {
+            Ok(self.state.delegation(address)?)
+        } else {
+            Ok(None)
+        }
+    }
+
     fn ret(self, gas: &U256, data: &ReturnData, apply_state: bool) -> vm::Result<U256>
     where
         Self: Sized,
@@ -362,9 +402,14 @@ where
 
         let address = self.origin_info.address.clone();
         let balance = self.balance(&address)?;
+        // since EIP-6780 only contracts created in the same transaction are destroyed, others
+        // just send their balance.
+        let destroy = !self.schedule.eip6780 || self.state.is_created_in_transaction(&address);
         if &address == refund_address {
             // TODO [todr] To be consistent with CPP client we set balance to 0 in that case.
-            self.state.sub_balance(&address, &balance, &mut CleanupMode::NoEmpty)?;
+            if destroy {
+                self.state.sub_balance(&address, &balance, &mut CleanupMode::NoEmpty)?;
+            }
         } else {
             self.state.transfer_balance(
                 &address,
@@ -375,7 +420,9 @@ where
         }
 
         self.tracer.trace_suicide(address, balance, refund_address.clone());
-        self.substate.suicides.insert(address);
+        if destroy {
+            self.substate.suicides.insert(address);
+        }
 
         Ok(())
     }
@@ -610,6 +657,44 @@ mod tests {
         assert_eq!(test_hash, hash);
     }
 
+    #[test]
+    fn can_return_block_hash_from_history_contract() {
+        let test_hash =
+            H256::from_str("afafafafafafafafafafafbcbcbcbcbcbcbcbcbcbeeeeeeeeeeeeedddddddddd")
+                .unwrap();
+        let test_env_number = 0x120001;
+
+        let mut setup = TestSetup::new();
+        setup.schedule.eip2935 = true;
+        setup.env_info.number = test_env_number;
+        let address = setup.machine.params().eip2935_contract_address;
+        setup.state.set_storage(&address, history_storage_key(0x120000), test_hash).unwrap();
+        let state = &mut setup.state;
+        let mut tracer = NoopTracer;
+        let mut vm_tracer = NoopVMTracer;
+        let origin_info = get_test_origin();
+
+        let mut ext = Externalities::new(
+            state,
+            &setup.env_info,
+            &setup.machine,
+            &setup.schedule,
+            0,
+            0,
+            &origin_info,
+            &mut setup.sub_state,
+            OutputPolicy::InitContract,
+            &mut tracer,
+            &mut vm_tracer,
+            false,
+        );
+
+        // last hashes are not consulted once the history contract is active
+        assert_eq!(ext.blockhash(&U256::from(0x120000)), test_hash);
+        assert_eq!(ext.blockhash(&U256::from(0x120001)), H256::zero());
+        assert_eq!(ext.blockhash(&U256::from(0x120001 - 257)), H256::zero());
+    }
+
     #[test]
     #[should_panic]
     fn can_call_fail_empty() {
@@ -0,0 +1,124 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compressed size estimation with FastLZ level 1.
+//!
+//! Only the length of the compressed output is computed, it is a cheap and deterministic
+//! estimate of how well transaction data compresses in a batch.
+
+use alloc::vec;
+
+const HASH_LOG: u32 = 13;
+const HASH_SIZE: usize = 1 << HASH_LOG;
+const MAX_COPY: usize = 32;
+const MAX_DISTANCE: usize = 8191;
+// a match is encoded with up to 262 bytes per 3 byte instruction
+const MATCH_CHUNK: usize = 262;
+
+/// Length of `data` compressed with FastLZ level 1.
+pub fn compress_len(data: &[u8]) -> usize {
+    let u24 =
+        |i: usize| u32::from(data[i]) | u32::from(data[i + 1]) << 8 | u32::from(data[i + 2]) << 16;
+    let hash = |v: u32| (v.wrapping_mul(2654435769) >> (32 - HASH_LOG)) as usize & (HASH_SIZE - 1);
+
+    let mut table = vec![0usize; HASH_SIZE];
+    let mut len = 0;
+    let mut anchor = 0;
+    let ip_limit = data.len().saturating_sub(13);
+    let mut ip = anchor + 2;
+    while ip < ip_limit {
+        // find the next match within the window
+        let mut reference;
+        loop {
+            let seq = u24(ip);
+            let h = hash(seq);
+            reference = table[h];
+            table[h] = ip;
+            let distance = ip - reference;
+            if ip >= ip_limit {
+                break;
+            }
+            ip += 1;
+            if distance <= MAX_DISTANCE && seq == u24(reference) {
+                break;
+            }
+        }
+        if ip >= ip_limit {
+            break;
+        }
+        ip -= 1;
+
+        if ip > anchor {
+            len += literals_len(ip - anchor);
+        }
+        let run = match_run(data, reference + 3, ip + 3, ip_limit + 9);
+        len += match_len(run);
+
+        ip += run;
+        for _ in 0..2 {
+            table[hash(u24(ip))] = ip;
+            ip += 1;
+        }
+        anchor = ip;
+    }
+
+    len + literals_len(data.len() - anchor)
+}
+
+/// Encoded length of `run` literal bytes, copied in chunks of `MAX_COPY` behind a marker.
+fn literals_len(run: usize) -> usize {
+    let rest = run % MAX_COPY;
+    (MAX_COPY + 1) * (run / MAX_COPY) + if rest != 0 { rest + 1 } else { 0 }
+}
+
+/// Encoded length of a match of `run` bytes.
+fn match_len(run: usize) -> usize {
+    let run = run - 1;
+    3 * (run / MATCH_CHUNK) + if run % MATCH_CHUNK >= 6 { 3 } else { 2 }
+}
+
+/// Number of bytes compared between `p` and `q` before `end`, including the first mismatch.
+fn match_run(data: &[u8], p: usize, q: usize, end: usize) -> usize {
+    let mut run = 0;
+    let mut limit = end - q;
+    while run < limit {
+        if data[p + run] != data[q + run] {
+            limit = 0;
+        }
+        run += 1;
+    }
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compress_len;
+
+    #[test]
+    fn literals_only() {
+        assert_eq!(compress_len(&[]), 0);
+        assert_eq!(compress_len(b"hello"), 6);
+        let distinct: Vec<u8> = (0..=255).collect();
+        assert_eq!(compress_len(&distinct), 264);
+    }
+
+    #[test]
+    fn repetitions_compress() {
+        assert_eq!(compress_len(&[0u8; 1000]), 21);
+        let repeated: Vec<u8> = (0..4).flat_map(|_| 0..=255u8).collect();
+        assert_eq!(compress_len(&repeated), 279);
+    }
+}
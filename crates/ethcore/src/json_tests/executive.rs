@@ -120,6 +120,14 @@ where
         self.ext.set_storage(key, value)
     }
 
+    fn transient_storage_at(&self, key: &H256) -> vm::Result<H256> {
+        self.ext.transient_storage_at(key)
+    }
+
+    fn set_transient_storage(&mut self, key: H256, value: H256) -> vm::Result<()> {
+        self.ext.set_transient_storage(key, value)
+    }
+
     fn exists(&self, address: &Address) -> vm::Result<bool> {
         self.ext.exists(address)
     }
@@ -184,6 +192,10 @@ where
         self.ext.extcodehash(address)
     }
 
+    fn delegation(&self, address: &Address) -> vm::Result<Option<Address>> {
+        self.ext.delegation(address)
+    }
+
     fn log(&mut self, topics: Vec<H256>, data: &[u8]) -> vm::Result<()> {
         self.ext.log(topics, data)
     }
@@ -53,6 +53,7 @@ pub mod verification;
 mod account_db;
 mod externalities;
 mod factory;
+mod fastlz;
 
 #[cfg(feature = "json-tests")]
 pub mod json_tests;
@@ -0,0 +1,56 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Allowlist of contract deployers kept in the storage of a system contract.
+//!
+//! A deployer is allowed when the storage slot keyed by its address holds a non-zero value.
+//! Entries are set in the genesis state or by state patches.
+
+use ethereum_types::{Address, H256};
+use ethtrie::Result as TrieResult;
+
+use crate::state::{Backend, State};
+
+/// Storage key of the allowlist entry of `deployer`.
+pub fn deployer_allowlist_key(deployer: &Address) -> H256 {
+    H256::from(*deployer)
+}
+
+/// Whether `deployer` may create contracts according to the allowlist held by `allowlist`.
+pub fn is_allowed_deployer<B: Backend>(
+    state: &State<B>, allowlist: &Address, deployer: &Address,
+) -> TrieResult<bool> {
+    Ok(!state.storage_at(allowlist, &deployer_allowlist_key(deployer))?.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::get_temp_state;
+
+    #[test]
+    fn allowed_deployer_has_non_zero_entry() {
+        let mut state = get_temp_state();
+        let allowlist = Address::from_low_u64_be(0x0103);
+        let deployer = Address::from_low_u64_be(0xd);
+        assert!(!is_allowed_deployer(&state, &allowlist, &deployer).unwrap());
+
+        let key = deployer_allowlist_key(&deployer);
+        state.set_storage(&allowlist, key, H256::from_low_u64_be(1)).unwrap();
+        assert!(is_allowed_deployer(&state, &allowlist, &deployer).unwrap());
+        assert!(!is_allowed_deployer(&state, &allowlist, &Address::zero()).unwrap());
+    }
+}
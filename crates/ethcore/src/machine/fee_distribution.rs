@@ -0,0 +1,134 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Distribution of the transaction fees collected over a block.
+
+use alloc::vec::Vec;
+use core::{convert::TryFrom, ops::AddAssign};
+use ethereum_types::{Address, U256, U512};
+
+use crate::engines::block_reward::RewardKind;
+
+/// Transaction fees, by the kind of fee.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    /// Fees paid above the base fee.
+    pub priority: U256,
+    /// Fees paid at the base fee.
+    pub base: U256,
+    /// L1 data fees.
+    pub l1: U256,
+}
+
+impl AddAssign for Fees {
+    fn add_assign(&mut self, other: Fees) {
+        self.priority = self.priority.saturating_add(other.priority);
+        self.base = self.base.saturating_add(other.base);
+        self.l1 = self.l1.saturating_add(other.l1);
+    }
+}
+
+/// Percentage shares of a kind of fee, paid to the given recipients.
+pub type FeeSplit = Vec<(Address, u8)>;
+
+/// Splits `amount` by the percentage `shares` and pushes the resulting rewards.
+///
+/// Shares are paid in order and capped to what is left, the remainder goes to `rest` with the
+/// `rest_kind` reward kind. Without `rest` the remainder is not paid to anyone.
+pub fn split_fee(
+    amount: U256, shares: &[(Address, u8)], rest: Option<(Address, RewardKind)>,
+    rewards: &mut Vec<(Address, RewardKind, U256)>,
+) {
+    let mut left = amount;
+    for &(recipient, percent) in shares {
+        let share = amount.full_mul(U256::from(percent)) / U512::from(100);
+        let share = if share > U512::from(left) {
+            left
+        } else {
+            U256::try_from(share).expect("share is lower than left, which fits U256; qed")
+        };
+        if !share.is_zero() {
+            rewards.push((recipient, RewardKind::External, share));
+            left -= share;
+        }
+    }
+    if let Some((recipient, kind)) = rest {
+        if !left.is_zero() {
+            rewards.push((recipient, kind, left));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn split_fee_pays_remainder_to_rest() {
+        let a = Address::from_low_u64_be(0xa);
+        let b = Address::from_low_u64_be(0xb);
+        let author = Address::from_low_u64_be(0xc);
+
+        let mut rewards = Vec::new();
+        split_fee(
+            U256::from(1001),
+            &[(a, 50), (b, 25)],
+            Some((author, RewardKind::Author)),
+            &mut rewards,
+        );
+        assert_eq!(
+            rewards,
+            vec![
+                (a, RewardKind::External, U256::from(500)),
+                (b, RewardKind::External, U256::from(250)),
+                (author, RewardKind::Author, U256::from(251)),
+            ]
+        );
+
+        // shares over 100% are capped, nothing is left for the rest
+        let mut rewards = Vec::new();
+        split_fee(
+            U256::from(100),
+            &[(a, 80), (b, 80)],
+            Some((author, RewardKind::Author)),
+            &mut rewards,
+        );
+        assert_eq!(
+            rewards,
+            vec![
+                (a, RewardKind::External, U256::from(80)),
+                (b, RewardKind::External, U256::from(20))
+            ]
+        );
+
+        // large amounts do not overflow
+        let mut rewards = Vec::new();
+        split_fee(U256::max_value(), &[(a, 50)], Some((author, RewardKind::Author)), &mut rewards);
+        assert_eq!(
+            rewards,
+            vec![
+                (a, RewardKind::External, U256::max_value() / 2),
+                (author, RewardKind::Author, U256::max_value() / 2 + 1),
+            ]
+        );
+
+        // without a recipient for the rest, it is burnt
+        let mut rewards = Vec::new();
+        split_fee(U256::from(100), &[(a, 10)], None, &mut rewards);
+        assert_eq!(rewards, vec![(a, RewardKind::External, U256::from(10))]);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-2935 block hash history kept in the storage of a system contract.
+
+use ethereum_types::{Address, H160, H256};
+use ethtrie::Result as TrieResult;
+use types::BlockNumber;
+
+use crate::state::{Backend, State};
+
+/// Number of block hashes kept by the history contract, older slots are overwritten.
+pub const HISTORY_SERVE_WINDOW: u64 = 8191;
+
+/// Default address of the history contract.
+pub const HISTORY_STORAGE_ADDRESS: Address = H160([
+    0x00, 0x00, 0xf9, 0x08, 0x27, 0xf1, 0xc5, 0x3a, 0x10, 0xcb, 0x7a, 0x02, 0x33, 0x5b, 0x17, 0x53,
+    0x20, 0x00, 0x29, 0x35,
+]);
+
+/// Runtime code of the history contract. Calls return the hash stored for the block number in
+/// the calldata, the system address writes the parent hash like `record_block_hashes` does.
+pub const HISTORY_STORAGE_CODE: [u8; 83] = [
+    0x33, 0x73, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0x14, 0x60, 0x46, 0x57, 0x60, 0x20, 0x36, 0x03, 0x60, 0x42,
+    0x57, 0x5f, 0x35, 0x60, 0x01, 0x43, 0x03, 0x81, 0x11, 0x60, 0x42, 0x57, 0x61, 0x1f, 0xff, 0x81,
+    0x43, 0x03, 0x11, 0x60, 0x42, 0x57, 0x61, 0x1f, 0xff, 0x90, 0x06, 0x54, 0x5f, 0x52, 0x60, 0x20,
+    0x5f, 0xf3, 0x5b, 0x5f, 0x5f, 0xfd, 0x5b, 0x5f, 0x35, 0x61, 0x1f, 0xff, 0x60, 0x01, 0x43, 0x03,
+    0x06, 0x55, 0x00,
+];
+
+/// Storage key holding the hash of block `number`.
+pub fn history_storage_key(number: BlockNumber) -> H256 {
+    H256::from_low_u64_be(number % HISTORY_SERVE_WINDOW)
+}
+
+/// Record the hashes of the ancestors of block `number`, most recent first like `LastHashes`.
+pub fn record_block_hashes<B: Backend>(
+    state: &mut State<B>, address: &Address, number: BlockNumber, hashes: &[H256],
+) -> TrieResult<()> {
+    for (i, hash) in hashes.iter().enumerate() {
+        let ancestor = match number.checked_sub(i as u64 + 1) {
+            Some(ancestor) => ancestor,
+            None => break,
+        };
+        state.set_storage(address, history_storage_key(ancestor), *hash)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::get_temp_state;
+
+    #[test]
+    fn record_block_hashes_in_window() {
+        let mut state = get_temp_state();
+        let address = HISTORY_STORAGE_ADDRESS;
+        let hashes = [H256::repeat_byte(3), H256::repeat_byte(2), H256::repeat_byte(1)];
+
+        record_block_hashes(&mut state, &address, HISTORY_SERVE_WINDOW + 3, &hashes).unwrap();
+        assert_eq!(state.storage_at(&address, &history_storage_key(2)).unwrap(), hashes[0]);
+        assert_eq!(state.storage_at(&address, &history_storage_key(1)).unwrap(), hashes[1]);
+        assert_eq!(state.storage_at(&address, &H256::zero()).unwrap(), hashes[2]);
+
+        // genesis has no ancestor
+        let mut state = get_temp_state();
+        record_block_hashes(&mut state, &address, 1, &hashes).unwrap();
+        assert_eq!(state.storage_at(&address, &H256::zero()).unwrap(), hashes[0]);
+        assert_eq!(
+            state.storage_at(&address, &history_storage_key(HISTORY_SERVE_WINDOW - 1)).unwrap(),
+            H256::zero()
+        );
+    }
+}
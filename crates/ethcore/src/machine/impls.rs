@@ -30,6 +30,11 @@ use vm::{CreateContractAddress, EnvInfo, Schedule};
 use crate::alloc::borrow::ToOwned;
 use crate::alloc::string::ToString;
 use crate::block::ExecutedBlock;
+use crate::engines::block_reward::RewardKind;
+use crate::machine::{
+    record_block_hashes, record_l1_origin, split_fee, StatePatch, HISTORY_STORAGE_CODE,
+    L1_ATTRIBUTES_CODE,
+};
 use crate::error::Error;
 use crate::spec::CommonParams;
 use crate::state::CleanupMode;
@@ -83,16 +88,28 @@ pub struct EthereumMachine {
     builtins: Arc<BTreeMap<Address, Builtin>>,
     ethash_extensions: Option<EthashExtensions>,
     schedule_rules: Option<Box<ScheduleCreationRules>>,
+    state_patches: BTreeMap<BlockNumber, Vec<StatePatch>>,
 }
 
 impl EthereumMachine {
     /// Regular ethereum machine.
     pub fn regular(params: CommonParams, builtins: BTreeMap<Address, Builtin>) -> EthereumMachine {
+        // the history contract is deployed with code using PUSH0
+        assert!(
+            params.eip2935_transition == BlockNumber::max_value()
+                || params.eip3855_transition <= params.eip2935_transition,
+            "EIP-2935 requires EIP-3855 to be active"
+        );
+        for split in &[&params.priority_fee_split, &params.base_fee_split, &params.l1_fee_split] {
+            let total: u32 = split.iter().map(|&(_, percent)| u32::from(percent)).sum();
+            assert!(total <= 100, "fee split shares sum to more than 100%");
+        }
         EthereumMachine {
             params,
             builtins: Arc::new(builtins),
             ethash_extensions: None,
             schedule_rules: None,
+            state_patches: BTreeMap::new(),
         }
     }
 
@@ -111,6 +128,11 @@ impl EthereumMachine {
         self.schedule_rules = Some(rules);
     }
 
+    /// Schedule irregular state changes, applied when the block of the given number is opened.
+    pub fn set_state_patches(&mut self, patches: BTreeMap<BlockNumber, Vec<StatePatch>>) {
+        self.state_patches = patches;
+    }
+
     /// Get a reference to the ethash-specific extensions.
     pub fn ethash_extensions(&self) -> Option<&EthashExtensions> {
         self.ethash_extensions.as_ref()
@@ -119,8 +141,45 @@ impl EthereumMachine {
 
 impl EthereumMachine {
     // t_nb 8.1.3 Logic to perform on a new block: updating last hashes and the DAO
-    /// fork, for ethash.
+    /// fork, for ethash, and applying the scheduled state patches.
     pub fn on_new_block(&self, block: &mut ExecutedBlock) -> Result<(), Error> {
+        if let Some(patches) = self.state_patches.get(&block.header.number()) {
+            let state = block.state_mut();
+            for patch in patches {
+                patch.apply(state)?;
+            }
+            block.state_patches = patches.clone();
+        }
+
+        let number = block.header.number();
+        if number >= self.params.eip2935_transition {
+            let address = self.params.eip2935_contract_address;
+            let parent_hash = *block.header.parent_hash();
+            let last_hashes = block.last_hashes.clone();
+            let state = block.state_mut();
+            if number == self.params.eip2935_transition {
+                // deploy the history contract and seed it with the hashes known so far
+                if state.code_size(&address)?.unwrap_or(0) == 0 {
+                    state.reset_code(&address, HISTORY_STORAGE_CODE.to_vec())?;
+                }
+                record_block_hashes(state, &address, number, &last_hashes)?;
+            }
+            record_block_hashes(state, &address, number, &[parent_hash])?;
+        }
+
+        if number >= self.params.l1_attributes_transition {
+            let address = self.params.l1_attributes_contract_address;
+            // blocks without a decodable origin record zeroed attributes
+            let origin = block.header.l1_origin().unwrap_or_default();
+            let state = block.state_mut();
+            if number == self.params.l1_attributes_transition
+                && state.code_size(&address)?.unwrap_or(0) == 0
+            {
+                state.reset_code(&address, L1_ATTRIBUTES_CODE.to_vec())?;
+            }
+            record_l1_origin(state, &address, &origin)?;
+        }
+
         if let Some(ref ethash_params) = self.ethash_extensions {
             if block.header.number() == ethash_params.dao_hardfork_transition {
                 let state = block.state_mut();
@@ -206,6 +265,27 @@ impl EthereumMachine {
         self.builtins().get(a).and_then(|b| if b.is_active(block_number) { Some(b) } else { None })
     }
 
+    /// Gas of a block available to enqueued transactions.
+    /// Returns `None` before enqueued transactions are prepaid on L1.
+    pub fn enqueued_gas_limit(&self, block_number: BlockNumber) -> Option<U256> {
+        if block_number >= self.params.enqueued_tx_transition {
+            Some(self.params.enqueued_gas_limit)
+        } else {
+            None
+        }
+    }
+
+    /// Address of the deployer allowlist contract, if the allowlist is enforced at the given block.
+    pub fn deployer_allowlist(&self, block_number: BlockNumber) -> Option<Address> {
+        if block_number >= self.params.deployer_allowlist_transition
+            && block_number < self.params.deployer_allowlist_disable_transition
+        {
+            Some(self.params.deployer_allowlist_contract_address)
+        } else {
+            None
+        }
+    }
+
     /// Some intrinsic operation parameters; by default they take their value from the `spec()`'s `engine_params`.
     pub fn maximum_extra_data_size(&self) -> usize {
         self.params().maximum_extra_data_size
@@ -258,7 +338,7 @@ impl EthereumMachine {
             if gas_limit > *header.gas_limit() {
                 return Err(GasLimitExceeded { limit: *header.gas_limit(), got: gas_limit });
             }
-            let base_gas_required = t.tx().gas_required(&self.params().schedule(header.number()));
+            let base_gas_required = t.tx().gas_required(&self.schedule(header.number()));
             if gas_limit.as_u64() < base_gas_required {
                 return Err(InsufficientGas {
                     minimal: U256::from(base_gas_required),
@@ -320,6 +400,9 @@ impl EthereumMachine {
             transaction::TypedTxId::EIP1559Transaction if !schedule.eip1559 => {
                 return Err(transaction::Error::TransactionTypeNotEnabled);
             }
+            transaction::TypedTxId::EIP7702Transaction if !schedule.eip7702 => {
+                return Err(transaction::Error::TransactionTypeNotEnabled);
+            }
             _ => (),
         };
 
@@ -429,6 +512,30 @@ impl super::Machine for EthereumMachine {
     ) -> Result<(), Error> {
         live.state_mut().add_balance(address, amount, CleanupMode::NoEmpty).map_err(Into::into)
     }
+
+    fn fee_rewards(&self, live: &ExecutedBlock) -> Vec<(Address, RewardKind, U256)> {
+        let number = live.header.number();
+        let fees = live.fees;
+        let mut rewards = Vec::new();
+        if number < self.params.fee_distribution_transition {
+            return rewards;
+        }
+
+        let sequencer = (*live.header.author(), RewardKind::Author);
+        split_fee(fees.priority, &self.params.priority_fee_split, Some(sequencer), &mut rewards);
+
+        // without a fee collector the base fee remainder is burnt
+        let collector = self
+            .params
+            .eip1559_fee_collector
+            .filter(|_| number >= self.params.eip1559_fee_collector_transition)
+            .map(|collector| (collector, RewardKind::External));
+        split_fee(fees.base, &self.params.base_fee_split, collector, &mut rewards);
+
+        let vault = (self.params.l1_fee_vault, RewardKind::External);
+        split_fee(fees.l1, &self.params.l1_fee_split, Some(vault), &mut rewards);
+        rewards
+    }
 }
 
 #[cfg(test)]
@@ -468,6 +575,36 @@ mod tests {
         assert_eq!(res, Err(transaction::Error::InvalidSignature));
     }
 
+    #[test]
+    fn blockhash_served_from_history_after_full_window() {
+        let mut params = new_london_test_machine().params().clone();
+        params.eip3855_transition = 0;
+        params.eip2935_transition = 10;
+        let machine = EthereumMachine::regular(params, Default::default());
+
+        assert!(!machine.schedule(10).eip2935);
+        assert!(!machine.schedule(265).eip2935);
+        assert!(machine.schedule(266).eip2935);
+    }
+
+    #[test]
+    #[should_panic(expected = "EIP-2935 requires EIP-3855 to be active")]
+    fn history_storage_requires_push0() {
+        let mut params = new_london_test_machine().params().clone();
+        params.eip3855_transition = 11;
+        params.eip2935_transition = 10;
+        EthereumMachine::regular(params, Default::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "fee split shares sum to more than 100%")]
+    fn fee_split_over_100_percent() {
+        let mut params = new_london_test_machine().params().clone();
+        params.base_fee_split =
+            vec![(Address::from_low_u64_be(1), 60), (Address::from_low_u64_be(2), 41)];
+        EthereumMachine::regular(params, Default::default());
+    }
+
     #[test]
     fn calculate_base_fee_success() {
         let machine = new_london_test_machine();
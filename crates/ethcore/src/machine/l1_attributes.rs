@@ -0,0 +1,98 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! L1 origin attributes of the current L2 block kept in the storage of a system contract.
+
+use ethereum_types::{Address, BigEndianHash, H256};
+use ethtrie::Result as TrieResult;
+use types::header::L1Origin;
+
+use crate::state::{Backend, State};
+
+/// Storage key of the batch index.
+pub const L1_BATCH_INDEX_KEY: u64 = 0;
+/// Storage key of the batch input hash.
+pub const L1_BATCH_INPUT_HASH_KEY: u64 = 1;
+/// Storage key of the first queue index consumed by the batch.
+pub const L1_QUEUE_START_KEY: u64 = 2;
+/// Storage key of the queue index following the batch.
+pub const L1_QUEUE_END_KEY: u64 = 3;
+/// Storage key of the L1 timestamp.
+pub const L1_TIMESTAMP_KEY: u64 = 4;
+/// Storage key of the L1 block number.
+pub const L1_BLOCK_NUMBER_KEY: u64 = 5;
+/// Storage key of the L1 base fee.
+pub const L1_BLOCK_BASE_FEE_KEY: u64 = 6;
+
+/// Runtime code of the attributes contract, returns the storage slot given as calldata.
+pub const L1_ATTRIBUTES_CODE: [u8; 12] =
+    [0x60, 0x00, 0x35, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+
+/// Write the L1 origin of the block being built into the attributes contract storage.
+pub fn record_l1_origin<B: Backend>(
+    state: &mut State<B>, address: &Address, origin: &L1Origin,
+) -> TrieResult<()> {
+    let attributes = [
+        (L1_BATCH_INDEX_KEY, H256::from_low_u64_be(origin.batch_index)),
+        (L1_BATCH_INPUT_HASH_KEY, origin.batch_input_hash),
+        (L1_QUEUE_START_KEY, H256::from_low_u64_be(origin.queue_start)),
+        (L1_QUEUE_END_KEY, H256::from_low_u64_be(origin.queue_end)),
+        (L1_TIMESTAMP_KEY, H256::from_low_u64_be(origin.l1_timestamp)),
+        (L1_BLOCK_NUMBER_KEY, H256::from_low_u64_be(origin.l1_block_number)),
+        (L1_BLOCK_BASE_FEE_KEY, BigEndianHash::from_uint(&origin.l1_base_fee)),
+    ];
+    for (key, value) in attributes.iter() {
+        state.set_storage(address, H256::from_low_u64_be(*key), *value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::get_temp_state;
+    use ethereum_types::U256;
+
+    #[test]
+    fn record_l1_origin_overwrites_previous_block() {
+        let mut state = get_temp_state();
+        let address = Address::from_low_u64_be(0x0101);
+        let origin = L1Origin {
+            batch_index: 3,
+            batch_input_hash: H256::repeat_byte(0xab),
+            queue_start: 10,
+            queue_end: 12,
+            l1_timestamp: 1_700_000_000,
+            l1_block_number: 18_500_000,
+            l1_base_fee: U256::from(30_000_000_000u64),
+        };
+        record_l1_origin(&mut state, &address, &origin).unwrap();
+
+        let slot = |state: &State<_>, key| state.storage_at(&address, &H256::from_low_u64_be(key));
+        assert_eq!(slot(&state, L1_BATCH_INDEX_KEY).unwrap(), H256::from_low_u64_be(3));
+        assert_eq!(slot(&state, L1_BATCH_INPUT_HASH_KEY).unwrap(), H256::repeat_byte(0xab));
+        assert_eq!(slot(&state, L1_QUEUE_START_KEY).unwrap(), H256::from_low_u64_be(10));
+        assert_eq!(slot(&state, L1_QUEUE_END_KEY).unwrap(), H256::from_low_u64_be(12));
+        assert_eq!(slot(&state, L1_TIMESTAMP_KEY).unwrap(), H256::from_low_u64_be(1_700_000_000));
+        assert_eq!(slot(&state, L1_BLOCK_NUMBER_KEY).unwrap(), H256::from_low_u64_be(18_500_000));
+        assert_eq!(slot(&state, L1_BLOCK_BASE_FEE_KEY).unwrap(), H256::from_low_u64_be(30_000_000_000));
+
+        record_l1_origin(&mut state, &address, &L1Origin::default()).unwrap();
+        assert_eq!(slot(&state, L1_BATCH_INPUT_HASH_KEY).unwrap(), H256::zero());
+        assert_eq!(slot(&state, L1_TIMESTAMP_KEY).unwrap(), H256::zero());
+        assert_eq!(slot(&state, L1_BLOCK_BASE_FEE_KEY).unwrap(), H256::zero());
+    }
+}
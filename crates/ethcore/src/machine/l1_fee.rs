@@ -0,0 +1,64 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! L1 data fee charged to transactions for posting them to L1.
+
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use ethtrie::Result as TrieResult;
+
+use crate::state::{Backend, State};
+
+/// Storage key of the L1 base fee in the fee oracle.
+pub const L1_BASE_FEE_KEY: u64 = 0;
+/// Storage key of the fee scalar in the fee oracle, in millionths.
+pub const L1_FEE_SCALAR_KEY: u64 = 1;
+/// Denominator of the fee scalar.
+pub const L1_FEE_SCALAR_DENOMINATOR: u64 = 1_000_000;
+/// L1 gas paid per byte of posted transaction data.
+pub const L1_GAS_PER_BYTE: u64 = 16;
+
+/// L1 data fee of a transaction encoded in `encoded_len` bytes, priced with the L1 base fee and
+/// scalar kept in the storage of `oracle`.
+pub fn l1_data_fee<B: Backend>(
+    state: &State<B>, oracle: &Address, encoded_len: usize,
+) -> TrieResult<U256> {
+    let base_fee = state.storage_at(oracle, &H256::from_low_u64_be(L1_BASE_FEE_KEY))?.into_uint();
+    let scalar = state.storage_at(oracle, &H256::from_low_u64_be(L1_FEE_SCALAR_KEY))?.into_uint();
+    let l1_gas = U256::from(encoded_len) * U256::from(L1_GAS_PER_BYTE);
+    let fee = l1_gas.saturating_mul(base_fee).saturating_mul(scalar);
+    Ok(fee / U256::from(L1_FEE_SCALAR_DENOMINATOR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::get_temp_state;
+
+    #[test]
+    fn l1_data_fee_follows_oracle() {
+        let mut state = get_temp_state();
+        let oracle = Address::from_low_u64_be(0x0102);
+        assert_eq!(l1_data_fee(&state, &oracle, 100).unwrap(), U256::zero());
+
+        let base_fee = H256::from_low_u64_be(30_000_000_000);
+        state.set_storage(&oracle, H256::from_low_u64_be(L1_BASE_FEE_KEY), base_fee).unwrap();
+        let scalar = H256::from_low_u64_be(500_000);
+        state.set_storage(&oracle, H256::from_low_u64_be(L1_FEE_SCALAR_KEY), scalar).unwrap();
+
+        // 100 bytes, 1600 L1 gas at 30 gwei, halved by the scalar
+        assert_eq!(l1_data_fee(&state, &oracle, 100).unwrap(), U256::from(24_000_000_000_000u64));
+    }
+}
@@ -1,7 +1,21 @@
 //! Generalization of a state machine for a consensus engine.
 
+mod deployer_allowlist;
+mod fee_distribution;
+mod history_storage;
 mod impls;
+mod l1_attributes;
+mod l1_fee;
+mod sequencer_registry;
+mod state_patch;
 mod traits;
 
+pub use self::deployer_allowlist::*;
+pub use self::fee_distribution::*;
+pub use self::history_storage::*;
 pub use self::impls::*;
+pub use self::l1_attributes::*;
+pub use self::l1_fee::*;
+pub use self::sequencer_registry::*;
+pub use self::state_patch::*;
 pub use self::traits::*;
@@ -0,0 +1,79 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Registry of the sequencers authorized to sign batches, kept in the storage of a system
+//! contract.
+//!
+//! Slot 0 holds the number of sequencers, slots 1 to that number hold their addresses. Entries
+//! are set in the genesis state or by state patches.
+
+use alloc::vec::Vec;
+use core::cmp;
+use ethereum_types::{Address, BigEndianHash, H256};
+use ethtrie::Result as TrieResult;
+
+use crate::state::{Backend, State};
+
+/// Maximum number of sequencers read from the registry, further entries are ignored.
+pub const MAX_SEQUENCERS: u64 = 16;
+
+/// Storage key of the number of registered sequencers.
+pub fn sequencer_count_key() -> H256 {
+    H256::zero()
+}
+
+/// Storage key of the sequencer at `index`, counted from zero.
+pub fn sequencer_key(index: u64) -> H256 {
+    H256::from_low_u64_be(index + 1)
+}
+
+/// Sequencers registered in `registry`, at most `MAX_SEQUENCERS`.
+pub fn registered_sequencers<B: Backend>(
+    state: &State<B>, registry: &Address,
+) -> TrieResult<Vec<Address>> {
+    let count = state.storage_at(registry, &sequencer_count_key())?.into_uint();
+    let count = cmp::min(count, MAX_SEQUENCERS.into()).as_u64();
+    (0..count)
+        .map(|index| Ok(Address::from(state.storage_at(registry, &sequencer_key(index))?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::get_temp_state;
+    use alloc::vec;
+
+    #[test]
+    fn registered_sequencers_capped() {
+        let mut state = get_temp_state();
+        let registry = Address::from_low_u64_be(0x0104);
+        assert!(registered_sequencers(&state, &registry).unwrap().is_empty());
+
+        let sequencer = Address::from_low_u64_be(0x5e);
+        state.set_storage(&registry, sequencer_key(0), H256::from(sequencer)).unwrap();
+        state.set_storage(&registry, sequencer_count_key(), H256::from_low_u64_be(1)).unwrap();
+        assert_eq!(registered_sequencers(&state, &registry).unwrap(), vec![sequencer]);
+
+        // a count over the maximum only reads the first entries
+        let count = H256::from_low_u64_be(MAX_SEQUENCERS + 1);
+        state.set_storage(&registry, sequencer_count_key(), count).unwrap();
+        let sequencers = registered_sequencers(&state, &registry).unwrap();
+        assert_eq!(sequencers.len() as u64, MAX_SEQUENCERS);
+        assert_eq!(sequencers[0], sequencer);
+        assert_eq!(sequencers[1], Address::zero());
+    }
+}
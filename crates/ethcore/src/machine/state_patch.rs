@@ -0,0 +1,102 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Irregular state changes scheduled at upgrade blocks.
+
+use bytes::Bytes;
+use ethereum_types::{Address, H256, U256};
+use ethtrie::Result as TrieResult;
+
+use crate::state::{Backend, CleanupMode, State};
+
+/// Irregular state change applied when a block is opened, before any transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatePatch {
+    /// Replace the code of an account, keeping its storage.
+    SetCode {
+        /// Patched account.
+        address: Address,
+        /// New code.
+        code: Bytes,
+    },
+    /// Set a storage slot of an account.
+    SetStorage {
+        /// Patched account.
+        address: Address,
+        /// Storage key.
+        key: H256,
+        /// New storage value.
+        value: H256,
+    },
+    /// Move balance between accounts, at most the balance of `from`.
+    MoveBalance {
+        /// Account the balance is taken from.
+        from: Address,
+        /// Account the balance is credited to.
+        to: Address,
+        /// Amount to move.
+        value: U256,
+    },
+}
+
+impl StatePatch {
+    /// Apply the patch to `state`.
+    pub fn apply<B: Backend>(&self, state: &mut State<B>) -> TrieResult<()> {
+        match self {
+            StatePatch::SetCode { address, code } => state.reset_code(address, code.clone()),
+            StatePatch::SetStorage { address, key, value } => {
+                state.set_storage(address, *key, *value)
+            }
+            StatePatch::MoveBalance { from, to, value } => {
+                let value = core::cmp::min(*value, state.balance(from)?);
+                state.transfer_balance(from, to, &value, CleanupMode::NoEmpty)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatePatch;
+    use crate::state::CleanupMode;
+    use crate::test_helpers::get_temp_state;
+    use ethereum_types::{Address, H256, U256};
+
+    #[test]
+    fn apply_state_patches() {
+        let mut state = get_temp_state();
+        let a = Address::from_low_u64_be(0xa);
+        let b = Address::from_low_u64_be(0xb);
+        state.add_balance(&a, &U256::from(100u64), CleanupMode::NoEmpty).unwrap();
+
+        StatePatch::SetCode { address: a, code: vec![0x60, 0x00] }.apply(&mut state).unwrap();
+        StatePatch::SetStorage {
+            address: a,
+            key: H256::from_low_u64_be(1),
+            value: H256::repeat_byte(2),
+        }
+        .apply(&mut state)
+        .unwrap();
+        StatePatch::MoveBalance { from: a, to: b, value: U256::from(1000u64) }
+            .apply(&mut state)
+            .unwrap();
+
+        assert_eq!(state.code(&a).unwrap().unwrap().as_ref(), &vec![0x60, 0x00]);
+        assert_eq!(state.storage_at(&a, &H256::from_low_u64_be(1)).unwrap(), H256::repeat_byte(2));
+        assert_eq!(state.balance(&a).unwrap(), U256::zero());
+        assert_eq!(state.balance(&b).unwrap(), U256::from(100u64));
+    }
+}
@@ -18,6 +18,8 @@
 //! This will define traits for the header, block, and state of a blockchain.
 
 use crate::block::ExecutedBlock;
+use crate::engines::block_reward::RewardKind;
+use alloc::vec::Vec;
 use ethereum_types::{Address, U256};
 
 /// Generalization of types surrounding blockchain-suitable state machines.
@@ -36,4 +38,9 @@ pub trait Machine: Send + Sync {
     fn add_balance(
         &self, live: &mut ExecutedBlock, address: &Address, amount: &U256,
     ) -> Result<(), Self::Error>;
+
+    /// Rewards paying out the transaction fees collected over the live block.
+    fn fee_rewards(&self, _live: &ExecutedBlock) -> Vec<(Address, RewardKind, U256)> {
+        Vec::new()
+    }
 }
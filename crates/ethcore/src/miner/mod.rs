@@ -20,29 +20,36 @@
 //! Keeps track of transactions and currently sealed pending block.
 
 use crate::block::{OpenBlock, SealedBlock};
-use crate::engines::EthEngine;
+use crate::engines::{compute_l2_seal, EthEngine};
 use crate::error::Error;
-use crate::ethereum::ethash::Seal;
 use crate::executed::ExecutionError;
 use crate::factory::{Factories, VmFactory};
+use crate::machine::{registered_sequencers, EthereumMachine};
+use crate::state::State;
 use crate::state_db::StateDB;
 use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use bytes::{Bytes, ToPretty};
-use ethereum_types::{Address, H64, U256};
-use hash::{keccak, H256};
+use ethereum_types::{Address, U256};
+use hash::H256;
 
 use ethtrie::TrieFactory;
 use evm::VMType;
 use hash_db::HashDB;
 use keccak_hasher::KeccakHasher;
 use trie::{DBValue, TrieSpec};
-use types::header::Header;
+use types::header::{Header, L2SealFields};
 use types::transaction;
 use types::transaction::UnverifiedTransaction;
 use vm::LastHashes;
 
+mod supply;
+
+pub use self::supply::SupplyReport;
+
 /// Riscv evm execution env.
 pub struct BlockGenInfo {
     ///
@@ -55,36 +62,220 @@ pub struct BlockGenInfo {
     pub gas_range_target: (U256, U256),
     ///
     pub extra_data: Bytes,
+    /// Maximum number of transactions included in the block, see `TransitionLimits`.
+    pub max_transactions: usize,
+    /// Whether transactions which don't fit into the block are returned to be carried over into
+    /// a follow-up block. Otherwise they are excluded, and the block is closed once less than
+    /// the gas of a plain transaction is left.
+    pub carry_over: bool,
 }
 
 const MB: usize = 1024 * 1024;
 
+/// Resource limits bounding the work of a single state transition, so a malicious input can
+/// not exhaust the memory or the step budget of the RISC-V guest.
+///
+/// Exceeding a limit is defined protocol behavior:
+/// - reading more than `max_preimage_bytes` of rollup input, ancestor headers included: the
+///   whole input is rejected and no block is produced.
+/// - more than `max_queue_entries` queue entries: the whole input is rejected and no block is
+///   produced, queue entries are never dropped.
+/// - more than `max_batches` batches: the batch data is rejected, only the queue transactions
+///   are applied.
+/// - a batch with more than `max_batch_transactions` transactions: that batch is dropped, the
+///   other batches and the queue transactions are applied.
+/// - decompressing more than `max_decompressed_bytes` of batch data in total: the batch data is
+///   rejected, only the queue transactions are applied.
+/// - more than `max_block_transactions` transactions in a block: the remaining ones are carried
+///   over into a follow-up block.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransitionLimits {
+    /// Maximum number of batches in the input.
+    pub max_batches: usize,
+    /// Maximum number of transactions in a single batch.
+    pub max_batch_transactions: usize,
+    /// Maximum number of bytes of all decompressed batch data.
+    pub max_decompressed_bytes: usize,
+    /// Maximum number of queue entries in the input.
+    pub max_queue_entries: usize,
+    /// Maximum number of preimage bytes read while loading the input.
+    pub max_preimage_bytes: usize,
+    /// Maximum number of transactions included in a single block.
+    pub max_block_transactions: usize,
+}
+
+impl Default for TransitionLimits {
+    fn default() -> Self {
+        TransitionLimits {
+            max_batches: 4096,
+            max_batch_transactions: 8192,
+            max_decompressed_bytes: 16 * MB,
+            max_queue_entries: 4096,
+            max_preimage_bytes: 64 * MB,
+            max_block_transactions: 4096,
+        }
+    }
+}
+
+impl TransitionLimits {
+    /// Limits which never apply, for rule sets predating the limits.
+    pub fn unbounded() -> Self {
+        TransitionLimits {
+            max_batches: usize::MAX,
+            max_batch_transactions: usize::MAX,
+            max_decompressed_bytes: usize::MAX,
+            max_queue_entries: usize::MAX,
+            max_preimage_bytes: usize::MAX,
+            max_block_transactions: usize::MAX,
+        }
+    }
+}
+
 impl BlockGenInfo {
     /// Create a new client with given parameters.
     pub fn new(
         parent_block_header: Header, last_hashes: Arc<LastHashes>, author: Address,
-        gas_range_target: (U256, U256), extra_data: Bytes,
+        gas_range_target: (U256, U256), extra_data: Bytes, max_transactions: usize,
+        carry_over: bool,
     ) -> BlockGenInfo {
-        BlockGenInfo { last_hashes, parent_block_header, author, gas_range_target, extra_data }
+        BlockGenInfo {
+            last_hashes,
+            parent_block_header,
+            author,
+            gas_range_target,
+            extra_data,
+            max_transactions,
+            carry_over,
+        }
+    }
+}
+
+/// Reason a transaction was left out of the produced blocks.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExclusionReason {
+    /// Transaction chain id is not the L2 chain id.
+    WrongChainId,
+    /// Sender could not be recovered from the signature.
+    Unrecoverable,
+    /// Batch transaction is signed by the L1 cross layer witness, which is reserved for enqueued
+    /// transactions.
+    ReservedSender,
+    /// Batch transaction nonce is in the range reserved for enqueued transactions.
+    ReservedNonce,
+    /// Transaction type is not accepted in batches.
+    UnsupportedType,
+    /// Transaction does not match the sender nonce in state.
+    NonceGap {
+        /// Nonce expected.
+        expected: U256,
+        /// Nonce found.
+        got: U256,
+    },
+    /// Transaction gas is higher than the gas limit of any block, or of the block being filled
+    /// when transactions are not carried over.
+    OutOfBlockGas,
+    /// Transaction was rejected by transaction verification, e.g. too big or exceeding the
+    /// exec gas limit.
+    Invalid(transaction::Error),
+    /// Transaction could not be executed.
+    Execution(ExecutionError),
+    /// No block could be opened on top of the parent, e.g. its state is not available.
+    NoBlock,
+    /// Transaction was rejected for any other reason.
+    Other(String),
+}
+
+impl From<Error> for ExclusionReason {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Execution(ExecutionError::InvalidNonce { expected, got }) => {
+                ExclusionReason::NonceGap { expected, got }
+            }
+            Error::Execution(ExecutionError::BlockGasLimitReached { .. }) => {
+                ExclusionReason::OutOfBlockGas
+            }
+            Error::Execution(e) => ExclusionReason::Execution(e),
+            Error::Transaction(e) => e.into(),
+            e => ExclusionReason::Other(format!("{}", e)),
+        }
+    }
+}
+
+impl From<transaction::Error> for ExclusionReason {
+    fn from(e: transaction::Error) -> Self {
+        match e {
+            transaction::Error::InvalidSignature => ExclusionReason::Unrecoverable,
+            transaction::Error::InvalidChainId => ExclusionReason::WrongChainId,
+            e => ExclusionReason::Invalid(e),
+        }
+    }
+}
+
+/// Transaction left out of the produced blocks.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExcludedTransaction {
+    /// Transaction hash.
+    pub hash: H256,
+    /// Why the transaction was excluded.
+    pub reason: ExclusionReason,
+}
+
+impl ExcludedTransaction {
+    /// Create a new excluded transaction record.
+    pub fn new(hash: H256, reason: ExclusionReason) -> Self {
+        ExcludedTransaction { hash, reason }
+    }
+}
+
+/// Result of `generate_block`.
+pub struct GeneratedBlock {
+    /// The sealed block.
+    pub block: SealedBlock,
+    /// Transactions which did not fit into the block gas limit, in their original order.
+    /// They should be carried over into a follow-up block with the same timestamp.
+    pub overflow: Vec<UnverifiedTransaction>,
+    /// Transactions which were dropped, with the reason.
+    pub excluded: Vec<ExcludedTransaction>,
+}
+
+fn factories() -> Factories {
+    Factories {
+        vm: VmFactory::new(VMType::Interpreter, MB),
+        trie: TrieFactory::new(TrieSpec::Secure),
+        accountdb: Default::default(),
     }
 }
 
+/// Sequencers registered in `registry` in the state of `parent`, see `registered_sequencers`.
+pub fn parent_sequencers(
+    db: Box<dyn HashDB<KeccakHasher, DBValue>>, machine: &EthereumMachine, parent: &Header,
+    registry: &Address,
+) -> Vec<Address> {
+    let state_db = StateDB::new(db, MB);
+    let start_nonce = machine.account_start_nonce(parent.number() + 1);
+    let state = State::from_existing(state_db, *parent.state_root(), start_nonce, factories())
+        .expect("parent state not found");
+    registered_sequencers(&state, registry).expect("sequencer registry not found")
+}
+
 /// generate and seal new block.
+///
+/// With `info.carry_over`, once a transaction would exceed the remaining block gas or the block
+/// already holds `info.max_transactions` transactions, it and every transaction after it are
+/// returned as overflow, so batch order (and sender nonce order) is preserved across the
+/// follow-up blocks. A transaction which could not fit even into an empty block is never
+/// carried over.
+///
+/// Returns `None` if the sealed block fails the engine verification.
 pub fn generate_block(
     db: Box<dyn HashDB<KeccakHasher, DBValue>>, engine: &impl EthEngine, info: &BlockGenInfo,
     txes: Vec<UnverifiedTransaction>, l2_witness_layer: Address,
-) -> Option<SealedBlock> {
-    let trie_factory = TrieFactory::new(TrieSpec::Secure);
-    let factories = Factories {
-        vm: VmFactory::new(VMType::Interpreter, MB),
-        trie: trie_factory,
-        accountdb: Default::default(),
-    };
+) -> Option<GeneratedBlock> {
     let state_db = StateDB::new(db, MB);
 
     let mut open_block = OpenBlock::new(
         engine,
-        factories,
+        factories(),
         true,
         state_db,
         &info.parent_block_header,
@@ -95,18 +286,39 @@ pub fn generate_block(
     )
     .ok()?;
 
-    let block_number = open_block.header.number();
-    let schedule = engine.schedule(block_number);
-    let min_tx_gas: U256 = schedule.tx_gas.into();
+    // a parent without L2 seal (genesis) starts the counters at zero
+    let parent_seal = info.parent_block_header.l2_seal().unwrap_or_default();
 
-    let event_sig = "MessageSent(uint64,address,address,bytes32,bytes)".as_bytes();
-    let event_id = keccak(event_sig);
-    let mut seal = Seal::parse_seal(info.parent_block_header.seal()).unwrap();
-
-    for transaction in txes {
+    let min_tx_gas: U256 = engine.schedule(open_block.header.number()).tx_gas.into();
+    let mut overflow = Vec::new();
+    let mut excluded = Vec::new();
+    let mut txes = txes.into_iter();
+    while let Some(transaction) = txes.next() {
+        let env_info = open_block.env_info();
+        let gas = transaction.tx().gas;
+        let enqueued_gas_limit = if transaction.tx().is_enqueued() {
+            engine.machine().enqueued_gas_limit(env_info.number)
+        } else {
+            None
+        };
+        if info.carry_over
+            && (open_block.transactions.len() >= info.max_transactions.max(1)
+                || (gas <= env_info.gas_limit && env_info.gas_used + gas > env_info.gas_limit)
+                || enqueued_gas_limit.map_or(false, |limit| {
+                    gas <= limit && open_block.enqueued_gas_used + gas > limit
+                }))
+        {
+            overflow.push(transaction);
+            overflow.extend(txes);
+            break;
+        }
+        let hash = transaction.hash();
         let transaction = {
             match engine.machine().verify_transaction_unordered(transaction, &open_block.header) {
-                Err(_) => continue,
+                Err(e) => {
+                    excluded.push(ExcludedTransaction::new(hash, e.into()));
+                    continue;
+                }
                 Ok(t) => t,
             }
         };
@@ -121,59 +333,58 @@ pub fn generate_block(
             Err(Error::Execution(ExecutionError::BlockGasLimitReached {
                 gas_limit,
                 gas_used,
-                gas: _,
-            })) => {
-                //debug!(target: "miner", "Skipping adding transaction to block because of gas limit: {:?} (limit: {:?}, used: {:?}, gas: {:?})", hash, gas_limit, gas_used, gas);
-                // Exit early if gas left is smaller then min_tx_gas
-                let gas_left = gas_limit - gas_used;
-                if gas_left < min_tx_gas {
-                    //debug!(target: "miner", "Remaining gas is lower than minimal gas for a transaction. Block is full.");
+                ..
+            })) if !info.carry_over => {
+                excluded.push(ExcludedTransaction::new(hash, ExclusionReason::OutOfBlockGas));
+                // the block is full once the gas left is smaller than min_tx_gas
+                if gas_limit - gas_used < min_tx_gas {
+                    excluded.extend(txes.map(|t| {
+                        ExcludedTransaction::new(t.hash(), ExclusionReason::OutOfBlockGas)
+                    }));
                     break;
                 }
             }
-            // Invalid nonce error can happen only if previous transaction is skipped because of gas limit.
-            // If there is errornous state of transaction queue it will be fixed when next block is imported.
-            Err(Error::Execution(ExecutionError::InvalidNonce { .. })) => {}
-            // already have transaction - ignore
-            Err(Error::Transaction(transaction::Error::AlreadyImported)) => {}
-            Err(Error::Transaction(transaction::Error::NotAllowed)) => {
-                //debug!(target: "miner", "Skipping non-allowed transaction for sender {:?}", hash);
-            }
-            Err(_e) => {
-                #[cfg(feature = "std")]
-                println!("push tx, {}", _e);
-
-                #[cfg(not(feature = "std"))]
-                riscv_evm::runtime::debug(alloc::format!("push tx, {}", _e).as_str());
-            }
+            // Invalid nonce error can happen if a previous transaction of the sender was excluded.
+            // Transactions that can never fit into a block end up here as well, fitting ones are
+            // spilled over above.
+            Err(e) => excluded.push(ExcludedTransaction::new(hash, ExclusionReason::from(e))),
             // imported ok
-            Ok(receipt) => {
-                for log in receipt.logs.iter() {
-                    if log.address == l2_witness_layer {
-                        if log.topics[0] == event_id {
-                            seal.nonce =
-                                H64::from_low_u64_be(U256::from(log.topics[1].as_bytes()).as_u64());
-                            seal.mix_hash = H256::from_slice(&log.data[0..32]);
-                        }
-                    }
-                }
-            }
+            Ok(_) => {}
         }
     }
 
     let closed_block = open_block.close();
     match closed_block {
         Ok(t) => {
-            let sealed_block = t
-                .lock()
-                .try_seal(
-                    engine,
-                    alloc::vec![
-                        ::rlp::encode(&seal.mix_hash).to_vec(),
-                        ::rlp::encode(&seal.nonce).to_vec()
-                    ],
+            #[cfg(feature = "supply-check")]
+            if let Some(ref diff) = t.balance_diff {
+                let report =
+                    SupplyReport::from_block(engine.machine(), &t, diff.clone(), l2_witness_layer);
+                if report.is_violated() {
+                    panic!("native ETH supply violated\n{}", report);
+                }
+            }
+            let seal =
+                compute_l2_seal(&parent_seal, &t.transactions, &t.receipts, l2_witness_layer);
+            // engines sealing with the legacy layout declare its field count
+            let seal_fields = if engine.seal_fields(&t.header) == L2SealFields::LEGACY_FIELDS {
+                seal.legacy_seal_fields()
+            } else {
+                seal.seal_fields()
+            };
+            let sealed_block = t.lock().try_seal(engine, seal_fields).expect("seal failed");
+            // a block breaking the engine rules is not produced
+            if engine
+                .verify_block_body(
+                    &sealed_block.header,
+                    &info.parent_block_header,
+                    &sealed_block.transactions,
+                    &sealed_block.receipts,
                 )
-                .expect("seal failed");
+                .is_err()
+            {
+                return None;
+            }
             #[cfg(feature = "std")]
             println!(
                 "{}: 0x{}, txNum: {}",
@@ -191,8 +402,94 @@ pub fn generate_block(
                 )
                 .as_str(),
             );
-            Some(sealed_block)
+            Some(GeneratedBlock { block: sealed_block, overflow, excluded })
         }
         Err(e) => panic!("{}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::NullEngine;
+    use crate::spec::Spec;
+    use crate::state::backend::ProofCheck;
+    use crypto::publickey::{Generator, Random};
+    use types::transaction::{Action, Transaction, TypedTransaction};
+
+    fn transactions(count: u64, gas: u64) -> Vec<UnverifiedTransaction> {
+        let keypair = Random.generate();
+        (0..count)
+            .map(|nonce| {
+                TypedTransaction::Legacy(Transaction {
+                    nonce: nonce.into(),
+                    gas_price: U256::zero(),
+                    gas: gas.into(),
+                    action: Action::Call(Address::zero()),
+                    value: U256::zero(),
+                    data: Vec::new(),
+                })
+                .sign(keypair.secret(), None)
+                .into()
+            })
+            .collect()
+    }
+
+    fn generate(
+        txs: Vec<UnverifiedTransaction>, max_transactions: usize, carry_over: bool,
+    ) -> GeneratedBlock {
+        let spec = Spec::new_test();
+        let engine = NullEngine::new(Default::default(), Spec::new_test_machine());
+        let db = spec.ensure_db_good(ProofCheck::new(&[]), &Default::default()).unwrap();
+        let genesis = spec.genesis_header();
+        let info = BlockGenInfo::new(
+            genesis.clone(),
+            Arc::new(alloc::vec![genesis.hash()]),
+            Address::zero(),
+            (*genesis.gas_limit(), *genesis.gas_limit()),
+            Vec::new(),
+            max_transactions,
+            carry_over,
+        );
+        generate_block(Box::new(db), &engine, &info, txs, Address::zero()).unwrap()
+    }
+
+    #[test]
+    fn max_block_transactions_carries_over() {
+        let txs = transactions(3, 21_000);
+        let generated = generate(txs.clone(), 2, true);
+        assert_eq!(generated.block.transactions.len(), 2);
+        assert_eq!(generated.overflow, txs[2..].to_vec());
+        assert!(generated.excluded.is_empty());
+    }
+
+    #[test]
+    fn block_gas_limit_carries_over() {
+        // the first transaction uses 21000 gas only, the following ones don't fit the rest of the
+        // 3141592 gas limit
+        let txs = transactions(3, 3_130_000);
+        let generated = generate(txs.clone(), usize::MAX, true);
+        assert_eq!(generated.block.transactions.len(), 1);
+        assert_eq!(generated.overflow, txs[1..].to_vec());
+        assert!(generated.excluded.is_empty());
+    }
+
+    #[test]
+    fn block_gas_limit_excludes_without_carry_over() {
+        let txs = transactions(3, 3_130_000);
+        let generated = generate(txs.clone(), usize::MAX, false);
+        assert_eq!(generated.block.transactions.len(), 1);
+        assert!(generated.overflow.is_empty());
+        // the block still has room, the sender of the dropped transaction is left with a gap
+        assert_eq!(
+            generated.excluded,
+            alloc::vec![
+                ExcludedTransaction::new(txs[1].hash(), ExclusionReason::OutOfBlockGas),
+                ExcludedTransaction::new(
+                    txs[2].hash(),
+                    ExclusionReason::NonceGap { expected: 1.into(), got: 2.into() }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Native ETH supply invariant of generated blocks.
+//!
+//! Enqueued deposits are the only way to mint L2 ETH, their value is locked on L1. ETH sent to
+//! the message passer is withdrawn, it is kept there until it is released on L1 and no longer
+//! counts to the supply. Fees are conserved apart from the part that is burnt, the base fee
+//! without a fee collector. The supply of a block thus changes by its deposits minus its
+//! withdrawals and burnt fees.
+
+use core::fmt;
+use ethereum_types::{Address, U256};
+use types::account_diff::Diff;
+use types::state_diff::StateDiff;
+use types::BlockNumber;
+
+use crate::block::ExecutedBlock;
+use crate::machine::{EthereumMachine, Fees, Machine};
+
+/// Native ETH supply change of a block, with the balance changes behind it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SupplyReport {
+    /// Number of the block.
+    pub number: BlockNumber,
+    /// Sum of the balance increases, the message passer left out.
+    pub increase: U256,
+    /// Sum of the balance decreases, the message passer left out.
+    pub decrease: U256,
+    /// Value minted by the enqueued deposits of the block.
+    pub deposits: U256,
+    /// Value withdrawn to the message passer.
+    pub withdrawals: U256,
+    /// Fees not credited to any account.
+    pub burnt: U256,
+    /// Transaction fees collected over the block.
+    pub fees: Fees,
+    /// Balance changes of the block.
+    pub diff: StateDiff,
+}
+
+impl SupplyReport {
+    /// Sum up the balance changes of a block, the balance gained by `message_passer` being
+    /// withdrawn.
+    pub fn new(
+        number: BlockNumber, diff: StateDiff, fees: Fees, deposits: U256, burnt: U256,
+        message_passer: Address,
+    ) -> Self {
+        let mut increase = U256::zero();
+        let mut decrease = U256::zero();
+        let mut withdrawals = U256::zero();
+        for (address, account) in diff.iter() {
+            let (gain, loss) = match account.balance {
+                Diff::Born(post) => (post, U256::zero()),
+                Diff::Died(pre) => (U256::zero(), pre),
+                Diff::Changed(pre, post) if post > pre => (post - pre, U256::zero()),
+                Diff::Changed(pre, post) => (U256::zero(), pre - post),
+                Diff::Same => continue,
+            };
+            if *address == message_passer {
+                // withdrawn value is only released on L1
+                withdrawals = withdrawals.saturating_add(gain);
+                decrease = decrease.saturating_add(loss);
+            } else {
+                increase = increase.saturating_add(gain);
+                decrease = decrease.saturating_add(loss);
+            }
+        }
+        SupplyReport { number, increase, decrease, deposits, withdrawals, burnt, fees, diff }
+    }
+
+    /// Account the balance changes of a closed block against its deposits, withdrawals and
+    /// burnt fees.
+    pub fn from_block(
+        machine: &EthereumMachine, block: &ExecutedBlock, diff: StateDiff, message_passer: Address,
+    ) -> Self {
+        let number = block.header.number();
+        let deposits = if number >= machine.params().enqueued_tx_transition {
+            block
+                .transactions
+                .iter()
+                .filter(|t| t.is_enqueued())
+                .fold(U256::zero(), |sum, t| sum.saturating_add(t.tx().value))
+        } else {
+            U256::zero()
+        };
+        let burnt = burnt_fees(machine, block);
+        SupplyReport::new(number, diff, block.fees, deposits, burnt, message_passer)
+    }
+
+    /// Whether the supply changed by anything but deposits, withdrawals and burnt fees, i.e.
+    /// whether `increase - decrease != deposits - withdrawals - burnt`.
+    pub fn is_violated(&self) -> bool {
+        // both sides moved over to stay unsigned
+        self.increase.saturating_add(self.withdrawals).saturating_add(self.burnt)
+            != self.decrease.saturating_add(self.deposits)
+    }
+}
+
+/// Fees of `block` not credited to any account.
+fn burnt_fees(machine: &EthereumMachine, block: &ExecutedBlock) -> U256 {
+    let params = machine.params();
+    let number = block.header.number();
+    let fees = block.fees;
+    if number >= params.fee_distribution_transition {
+        // fees are paid out when the block closes, the rest is burnt
+        let total = fees.priority.saturating_add(fees.base).saturating_add(fees.l1);
+        let paid = machine
+            .fee_rewards(block)
+            .iter()
+            .fold(U256::zero(), |sum, (_, _, amount)| sum.saturating_add(*amount));
+        return total.saturating_sub(paid);
+    }
+    // the executive burns the base fee unless a fee collector takes it
+    let collector =
+        params.eip1559_fee_collector.is_some() && number >= params.eip1559_fee_collector_transition;
+    if collector {
+        U256::zero()
+    } else {
+        fees.base
+    }
+}
+
+impl fmt::Display for SupplyReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "block {} changed the ETH supply by +{} -{}, deposits {}, withdrawals {}, burnt {}",
+            self.number, self.increase, self.decrease, self.deposits, self.withdrawals, self.burnt
+        )?;
+        writeln!(
+            f,
+            "  fees: priority {}, base {}, l1 {}",
+            self.fees.priority, self.fees.base, self.fees.l1
+        )?;
+        for (address, account) in self.diff.iter() {
+            let pre = account.balance.pre().cloned().unwrap_or_default();
+            let post = account.balance.post().cloned().unwrap_or_default();
+            writeln!(f, "  {:?}: {} -> {}", address, pre, post)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use types::account_diff::AccountDiff;
+
+    fn balance_diff(balance: Diff<U256>) -> AccountDiff {
+        AccountDiff { balance, nonce: Diff::Same, code: Diff::Same, storage: BTreeMap::new() }
+    }
+
+    fn state_diff(balances: Vec<(u64, Diff<U256>)>) -> StateDiff {
+        let raw = balances
+            .into_iter()
+            .map(|(address, balance)| (Address::from_low_u64_be(address), balance_diff(balance)))
+            .collect();
+        StateDiff { raw }
+    }
+
+    const PASSER: u64 = 0x221;
+
+    #[test]
+    fn supply_report_accounts_burnt_fees() {
+        // 60 paid in fees, 50 credited to the author and 10 burnt
+        let diff =
+            state_diff(vec![(1, Diff::Changed(100.into(), 40.into())), (2, Diff::Born(50.into()))]);
+        let fees = Fees { priority: 50.into(), base: 10.into(), l1: 0.into() };
+        let passer = Address::from_low_u64_be(PASSER);
+        let report = SupplyReport::new(1, diff.clone(), fees, 0.into(), 10.into(), passer);
+        assert_eq!(report.increase, U256::from(50));
+        assert_eq!(report.decrease, U256::from(60));
+        assert!(!report.is_violated());
+
+        // burning less than the block did is flagged as well
+        let report = SupplyReport::new(1, diff, fees, 0.into(), 0.into(), passer);
+        assert!(report.is_violated());
+    }
+
+    #[test]
+    fn supply_report_accounts_deposits() {
+        // a deposit of 30 minted to its sender and sent along
+        let diff = state_diff(vec![(1, Diff::Same), (2, Diff::Born(30.into()))]);
+        let passer = Address::from_low_u64_be(PASSER);
+        let report =
+            SupplyReport::new(1, diff.clone(), Fees::default(), 30.into(), 0.into(), passer);
+        assert_eq!(report.increase, U256::from(30));
+        assert_eq!(report.deposits, U256::from(30));
+        assert!(!report.is_violated());
+
+        // the same balance change without deposit mints ETH
+        let report = SupplyReport::new(1, diff, Fees::default(), 0.into(), 0.into(), passer);
+        assert!(report.is_violated());
+    }
+
+    #[test]
+    fn supply_report_accounts_withdrawals() {
+        // 25 sent to the message passer
+        let diff = state_diff(vec![
+            (1, Diff::Changed(100.into(), 75.into())),
+            (PASSER, Diff::Changed(5.into(), 30.into())),
+        ]);
+        let passer = Address::from_low_u64_be(PASSER);
+        let report =
+            SupplyReport::new(1, diff.clone(), Fees::default(), 0.into(), 0.into(), passer);
+        assert_eq!(report.increase, U256::zero());
+        assert_eq!(report.decrease, U256::from(25));
+        assert_eq!(report.withdrawals, U256::from(25));
+        assert!(!report.is_violated());
+
+        // sent to any other account the value only moves
+        let other = Address::from_low_u64_be(3);
+        let report = SupplyReport::new(1, diff, Fees::default(), 0.into(), 0.into(), other);
+        assert_eq!(report.increase, U256::from(25));
+        assert_eq!(report.withdrawals, U256::zero());
+        assert!(!report.is_violated());
+    }
+}
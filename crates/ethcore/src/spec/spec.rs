@@ -19,6 +19,8 @@
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::sync::Arc;
 use core::cell::RefCell;
+#[cfg(feature = "std")]
+use core::cmp;
 use core::convert::TryFrom;
 #[cfg(feature = "std")]
 use std::io::Read;
@@ -28,6 +30,8 @@ use ethereum_types::{Address, Bloom, H256, U256};
 use hash::{keccak, KECCAK_NULL_RLP};
 use rlp::{Rlp, RlpStream};
 use types::header::Header;
+#[cfg(feature = "std")]
+use types::l2_cfg::{L2_DEPLOYER_ALLOWLIST, L2_L1_ATTRIBUTES, L2_L1_FEE_ORACLE, L2_L1_FEE_VAULT};
 use types::BlockNumber;
 use vm::{AccessList, ActionParams, ActionValue, CallType, EnvInfo, ParamsType};
 
@@ -37,7 +41,9 @@ use crate::error::Error;
 use crate::ethereum;
 use crate::executive::Executive;
 use crate::factory::Factories;
-use crate::machine::EthereumMachine;
+use crate::machine::{EthereumMachine, FeeSplit};
+#[cfg(feature = "std")]
+use crate::machine::HISTORY_STORAGE_ADDRESS;
 use crate::pod_state::PodState;
 use crate::spec::seal::Generic as GenericSeal;
 use crate::spec::Genesis;
@@ -130,8 +136,6 @@ pub struct CommonParams {
     pub eip1884_transition: BlockNumber,
     /// Number of first block where EIP-2028 rules begin.
     pub eip2028_transition: BlockNumber,
-    /// Number of first block where EIP-2315 rules begin.
-    pub eip2315_transition: BlockNumber,
     /// Number of first block where EIP-2929 rules begin.
     pub eip2929_transition: BlockNumber,
     /// Number of first block where EIP-2930 rules begin.
@@ -146,6 +150,62 @@ pub struct CommonParams {
     pub eip3541_transition: BlockNumber,
     /// Number of first block where EIP-3607 rule begins.
     pub eip3607_transition: BlockNumber,
+    /// Number of first block where EIP-3651 rule begins. Warm coinbase.
+    pub eip3651_transition: BlockNumber,
+    /// Number of first block where EIP-3855 rule begins. PUSH0 opcode.
+    pub eip3855_transition: BlockNumber,
+    /// Number of first block where EIP-3860 rules begin. Limit and meter initcode.
+    pub eip3860_transition: BlockNumber,
+    /// Number of first block where EIP-1153 rules begin. Transient storage.
+    pub eip1153_transition: BlockNumber,
+    /// Number of first block where EIP-5656 rule begins. MCOPY opcode.
+    pub eip5656_transition: BlockNumber,
+    /// Number of first block where EIP-6780 rules begin. SELFDESTRUCT only in same transaction.
+    pub eip6780_transition: BlockNumber,
+    /// Number of first block where EIP-7702 rules begin. Set-code transactions.
+    pub eip7702_transition: BlockNumber,
+    /// Number of first block where EIP-2935 rules begin. Block hashes recorded in state, and
+    /// served from it 256 blocks later. Not before `eip3855_transition`.
+    pub eip2935_transition: BlockNumber,
+    /// Address of the EIP-2935 block hash history contract.
+    pub eip2935_contract_address: Address,
+    /// Number of first block where the L1 origin is written into the attributes contract.
+    pub l1_attributes_transition: BlockNumber,
+    /// Address of the L1 attributes contract.
+    pub l1_attributes_contract_address: Address,
+    /// Number of first block where transactions pay the L1 data fee.
+    pub l1_fee_transition: BlockNumber,
+    /// Address of the contract holding the L1 base fee and fee scalar.
+    pub l1_fee_oracle: Address,
+    /// Account credited with L1 data fees.
+    pub l1_fee_vault: Address,
+    /// Multiplier of the transaction data gas.
+    pub tx_data_gas_factor: usize,
+    /// Bytes of transaction envelope charged as non-zero data bytes.
+    pub tx_base_size: usize,
+    /// Number of first block where transaction data is charged by its estimated compressed size.
+    pub tx_data_compression_transition: BlockNumber,
+    /// Number of first block where transaction fees are collected over the block and
+    /// distributed when it closes.
+    pub fee_distribution_transition: BlockNumber,
+    /// Shares of the priority fees paid away from the block author, in percent, at most 100 in total.
+    pub priority_fee_split: FeeSplit,
+    /// Shares of the base fees paid away from the EIP-1559 fee collector, in percent, at most 100 in total.
+    pub base_fee_split: FeeSplit,
+    /// Shares of the L1 data fees paid away from the L1 fee vault, in percent, at most 100 in total.
+    pub l1_fee_split: FeeSplit,
+    /// Number of first block where enqueued transactions are prepaid on L1. They pay no fee on
+    /// L2, their value is minted to their sender, their gas is capped per block and their
+    /// receipts are marked as deposits.
+    pub enqueued_tx_transition: BlockNumber,
+    /// Gas of a block available to enqueued transactions.
+    pub enqueued_gas_limit: U256,
+    /// Number of first block where contract creation is restricted to allowlisted deployers.
+    pub deployer_allowlist_transition: BlockNumber,
+    /// Number of first block where contract creation is open to anyone again.
+    pub deployer_allowlist_disable_transition: BlockNumber,
+    /// Address of the contract holding the deployer allowlist.
+    pub deployer_allowlist_contract_address: Address,
     /// Number of first block where dust cleanup rules (EIP-168 and EIP169) begin.
     pub dust_protection_transition: BlockNumber,
     /// Nonce cap increase per block. Nonce cap is only checked if dust protection is enabled.
@@ -223,12 +283,23 @@ impl CommonParams {
             && !(block_number >= self.eip1283_disable_transition))
             || block_number >= self.eip1283_reenable_transition;
         schedule.eip1706 = block_number >= self.eip1706_transition;
-        schedule.have_subs = block_number >= self.eip2315_transition;
         schedule.eip2929 = block_number >= self.eip2929_transition;
         schedule.eip2930 = block_number >= self.eip2930_transition;
         schedule.eip3541 = block_number >= self.eip3541_transition;
         schedule.eip1559 = block_number >= self.eip1559_transition;
         schedule.eip3198 = block_number >= self.eip3198_transition;
+        schedule.eip3651 = block_number >= self.eip3651_transition;
+        schedule.have_push0 = block_number >= self.eip3855_transition;
+        schedule.eip3860 = block_number >= self.eip3860_transition;
+        schedule.have_transient_storage = block_number >= self.eip1153_transition;
+        schedule.have_mcopy = block_number >= self.eip5656_transition;
+        schedule.eip6780 = block_number >= self.eip6780_transition;
+        schedule.eip7702 = block_number >= self.eip7702_transition;
+        // BLOCKHASH reads the history contract once it recorded a full window of 256 hashes
+        schedule.eip2935 = block_number >= self.eip2935_transition.saturating_add(256);
+        schedule.tx_data_gas_factor = self.tx_data_gas_factor;
+        schedule.tx_base_size = self.tx_base_size;
+        schedule.tx_data_compressed = block_number >= self.tx_data_compression_transition;
         if schedule.eip1559 {
             schedule.eip1559_elasticity_multiplier = self.eip1559_elasticity_multiplier.as_usize();
 
@@ -270,6 +341,9 @@ impl CommonParams {
             schedule.sstore_refund_gas = ::vm::schedule::EIP3529_SSTORE_CLEARS_SCHEDULE;
             schedule.max_refund_quotient = ::vm::schedule::EIP3529_MAX_REFUND_QUOTIENT;
         }
+        if block_number >= self.eip3860_transition {
+            schedule.initcode_word_gas = ::vm::schedule::EIP3860_INITCODE_WORD_GAS;
+        }
 
         if block_number >= self.dust_protection_transition {
             schedule.kill_dust = match self.remove_dust_contracts {
@@ -297,6 +371,17 @@ impl CommonParams {
     }
 }
 
+#[cfg(feature = "std")]
+// helper for converting fee shares, capped at 100 percent each.
+fn fee_split(shares: BTreeMap<ethjson::hash::Address, ethjson::uint::Uint>) -> FeeSplit {
+    shares
+        .into_iter()
+        .map(|(recipient, percent)| {
+            (recipient.into(), cmp::min(Into::<u64>::into(percent), 100) as u8)
+        })
+        .collect()
+}
+
 #[cfg(feature = "std")]
 impl From<ethjson::spec::Params> for CommonParams {
     fn from(p: ethjson::spec::Params) -> Self {
@@ -352,9 +437,6 @@ impl From<ethjson::spec::Params> for CommonParams {
             eip2028_transition: p
                 .eip2028_transition
                 .map_or_else(BlockNumber::max_value, Into::into),
-            eip2315_transition: p
-                .eip2315_transition
-                .map_or_else(BlockNumber::max_value, Into::into),
             eip2929_transition: p
                 .eip2929_transition
                 .map_or_else(BlockNumber::max_value, Into::into),
@@ -377,6 +459,66 @@ impl From<ethjson::spec::Params> for CommonParams {
                 .dust_protection_transition
                 .map_or_else(BlockNumber::max_value, Into::into),
             eip3607_transition: p.eip3607_transition.map_or(0, Into::into),
+            eip3651_transition: p
+                .eip3651_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip3855_transition: p
+                .eip3855_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip3860_transition: p
+                .eip3860_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip1153_transition: p
+                .eip1153_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip5656_transition: p
+                .eip5656_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip6780_transition: p
+                .eip6780_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip7702_transition: p
+                .eip7702_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip2935_transition: p
+                .eip2935_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            eip2935_contract_address: p
+                .eip2935_contract_address
+                .map_or(HISTORY_STORAGE_ADDRESS, Into::into),
+            l1_attributes_transition: p
+                .l1_attributes_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            l1_attributes_contract_address: p
+                .l1_attributes_contract_address
+                .map_or(L2_L1_ATTRIBUTES, Into::into),
+            l1_fee_transition: p.l1_fee_transition.map_or_else(BlockNumber::max_value, Into::into),
+            l1_fee_oracle: p.l1_fee_oracle.map_or(L2_L1_FEE_ORACLE, Into::into),
+            l1_fee_vault: p.l1_fee_vault.map_or(L2_L1_FEE_VAULT, Into::into),
+            tx_data_gas_factor: p.tx_data_gas_factor.map_or(1, Into::into),
+            tx_base_size: p.tx_base_size.map_or(0, Into::into),
+            tx_data_compression_transition: p
+                .tx_data_compression_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            fee_distribution_transition: p
+                .fee_distribution_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            priority_fee_split: p.priority_fee_split.map_or_else(Vec::new, fee_split),
+            base_fee_split: p.base_fee_split.map_or_else(Vec::new, fee_split),
+            l1_fee_split: p.l1_fee_split.map_or_else(Vec::new, fee_split),
+            enqueued_tx_transition: p
+                .enqueued_tx_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            enqueued_gas_limit: p.enqueued_gas_limit.map_or_else(U256::max_value, Into::into),
+            deployer_allowlist_transition: p
+                .deployer_allowlist_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            deployer_allowlist_disable_transition: p
+                .deployer_allowlist_disable_transition
+                .map_or_else(BlockNumber::max_value, Into::into),
+            deployer_allowlist_contract_address: p
+                .deployer_allowlist_contract_address
+                .map_or(L2_DEPLOYER_ALLOWLIST, Into::into),
             nonce_cap_increment: p.nonce_cap_increment.map_or(64, Into::into),
             remove_dust_contracts: p.remove_dust_contracts.unwrap_or(false),
             gas_limit_bound_divisor: p.gas_limit_bound_divisor.into(),
@@ -618,13 +760,27 @@ impl Spec {
             params.eip1344_transition,
             params.eip1884_transition,
             params.eip2028_transition,
-            params.eip2315_transition,
             params.eip2929_transition,
             params.eip2930_transition,
             params.eip1559_transition,
             params.eip3198_transition,
             params.eip3529_transition,
             params.eip3541_transition,
+            params.eip3651_transition,
+            params.eip3855_transition,
+            params.eip3860_transition,
+            params.eip1153_transition,
+            params.eip5656_transition,
+            params.eip6780_transition,
+            params.eip7702_transition,
+            params.eip2935_transition,
+            params.l1_attributes_transition,
+            params.l1_fee_transition,
+            params.tx_data_compression_transition,
+            params.fee_distribution_transition,
+            params.enqueued_tx_transition,
+            params.deployer_allowlist_transition,
+            params.deployer_allowlist_disable_transition,
             params.dust_protection_transition,
             params.kip4_transition,
             params.kip6_transition,
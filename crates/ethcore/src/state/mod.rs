@@ -31,17 +31,19 @@ use crate::error::Error;
 use crate::executed::{Executed, ExecutionError};
 use crate::executive::{Executive, TransactOptions};
 use crate::factory::{Factories, VmFactory};
-use crate::machine::EthereumMachine as Machine;
+use crate::machine::{EthereumMachine as Machine, Fees};
 use crate::pod_account::*;
 use crate::pod_state::{self, PodState};
 use crate::state_db::StateDB;
 use crate::trace::{self, FlatTrace, VMTrace};
+#[cfg(feature = "supply-check")]
+use types::account_diff::{AccountDiff, Diff};
 use types::basic_account::BasicAccount;
 use types::receipt::{LegacyReceipt, TransactionOutcome, TypedReceipt};
 use types::state_diff::StateDiff;
 use types::transaction::SignedTransaction;
 
-use vm::EnvInfo;
+use vm::{delegation, EnvInfo};
 
 use bytes::Bytes;
 use ethereum_types::{Address, H256, U256};
@@ -73,6 +75,8 @@ pub struct ApplyOutcome<T, V> {
     pub trace: Vec<T>,
     /// The VM trace for the applied transaction, None if tracing was not produced.
     pub vm_trace: Option<V>,
+    /// The fees paid by the applied transaction.
+    pub fees: Fees,
 }
 
 /// Result type for the execution ("application") of a transaction.
@@ -291,6 +295,12 @@ pub struct State<B> {
     cache: RefCell<HashMap<Address, AccountEntry>>,
     // The original account is preserved in
     checkpoints: RefCell<Vec<HashMap<Address, Option<AccountEntry>>>>,
+    // EIP-1153 transient storage of the current transaction
+    transient_storage: HashMap<(Address, H256), H256>,
+    // The original transient values are preserved in, in lockstep with `checkpoints`
+    transient_checkpoints: Vec<HashMap<(Address, H256), H256>>,
+    // contracts created by the current transaction, EIP-6780
+    created_contracts: HashSet<Address>,
     account_start_nonce: U256,
     factories: Factories,
 }
@@ -361,6 +371,9 @@ impl<B: Backend> State<B> {
             root: root,
             cache: RefCell::new(HashMap::new()),
             checkpoints: RefCell::new(Vec::new()),
+            transient_storage: HashMap::new(),
+            transient_checkpoints: Vec::new(),
+            created_contracts: HashSet::new(),
             account_start_nonce: account_start_nonce,
             factories: factories,
         }
@@ -382,6 +395,9 @@ impl<B: Backend> State<B> {
             root: root,
             cache: cache,
             checkpoints: checkpoints,
+            transient_storage: HashMap::new(),
+            transient_checkpoints: Vec::new(),
+            created_contracts: HashSet::new(),
             account_start_nonce: account_start_nonce,
             factories: factories,
         };
@@ -399,6 +415,7 @@ impl<B: Backend> State<B> {
         let checkpoints = self.checkpoints.get_mut();
         let index = checkpoints.len();
         checkpoints.push(HashMap::new());
+        self.transient_checkpoints.push(HashMap::new());
         index
     }
 
@@ -417,6 +434,13 @@ impl<B: Backend> State<B> {
                 }
             }
         }
+        if let Some(checkpoint) = self.transient_checkpoints.pop() {
+            if let Some(prev) = self.transient_checkpoints.last_mut() {
+                for (k, v) in checkpoint {
+                    prev.entry(k).or_insert(v);
+                }
+            }
+        }
     }
 
     /// Revert to the last checkpoint and discard it.
@@ -446,6 +470,46 @@ impl<B: Backend> State<B> {
                 }
             }
         }
+        if let Some(checkpoint) = self.transient_checkpoints.pop() {
+            for (k, v) in checkpoint {
+                if v.is_zero() {
+                    self.transient_storage.remove(&k);
+                } else {
+                    self.transient_storage.insert(k, v);
+                }
+            }
+        }
+    }
+
+    /// Reset the data scoped to a single transaction: the EIP-1153 transient storage and the
+    /// contracts created by the transaction.
+    pub fn begin_transaction(&mut self) {
+        self.transient_storage.clear();
+        self.created_contracts.clear();
+    }
+
+    /// Get the value of transient storage of `contract` at `key`, EIP-1153.
+    pub fn transient_storage_at(&self, contract: &Address, key: &H256) -> H256 {
+        self.transient_storage.get(&(*contract, *key)).cloned().unwrap_or_default()
+    }
+
+    /// Mutate transient storage of `contract` at `key` to `value`, EIP-1153.
+    pub fn set_transient_storage(&mut self, contract: &Address, key: H256, value: H256) {
+        let k = (*contract, key);
+        let old = self.transient_storage.get(&k).cloned().unwrap_or_default();
+        if let Some(checkpoint) = self.transient_checkpoints.last_mut() {
+            checkpoint.entry(k).or_insert(old);
+        }
+        if value.is_zero() {
+            self.transient_storage.remove(&k);
+        } else {
+            self.transient_storage.insert(k, value);
+        }
+    }
+
+    /// Whether `contract` was created by the current transaction, EIP-6780.
+    pub fn is_created_in_transaction(&self, contract: &Address) -> bool {
+        self.created_contracts.contains(contract)
     }
 
     fn insert_cache(&self, address: &Address, account: AccountEntry) {
@@ -512,6 +576,7 @@ impl<B: Backend> State<B> {
                 original_storage_root,
             ))),
         );
+        self.created_contracts.insert(*contract);
         Ok(())
     }
 
@@ -760,6 +825,21 @@ impl<B: Backend> State<B> {
         self.ensure_cached(a, RequireCache::CodeSize, |a| a.as_ref().and_then(|a| a.code_size()))
     }
 
+    /// Get the EIP-7702 delegation target of account `a`, if its code is a delegation designator.
+    pub fn delegation(&self, a: &Address) -> TrieResult<Option<Address>> {
+        if self.code_size(a)? != Some(delegation::DELEGATION_DESIGNATOR_LEN) {
+            return Ok(None);
+        }
+        Ok(self.code(a)?.and_then(|code| delegation::target(&code)))
+    }
+
+    /// Get the code and code hash executed when calling account `a`.
+    /// A delegation designator is followed one level to the code of its target.
+    pub fn delegated_code(&self, a: &Address) -> TrieResult<(Option<Arc<Bytes>>, Option<H256>)> {
+        let code_address = self.delegation(a)?.unwrap_or(*a);
+        Ok((self.code(&code_address)?, self.code_hash(&code_address)?))
+    }
+
     /// Add `incr` to the balance of account `a`.
     pub fn add_balance(
         &mut self, a: &Address, incr: &U256, cleanup_mode: CleanupMode,
@@ -882,12 +962,15 @@ impl<B: Backend> State<B> {
         };
 
         let output = e.output;
-        let receipt = TypedReceipt::new(
-            t.tx_type(),
-            LegacyReceipt::new(outcome, e.cumulative_gas_used, e.logs),
-        );
+        let fees = Fees { priority: e.priority_fee, base: e.base_fee, l1: e.l1_fee };
+        let mut receipt = LegacyReceipt::new(outcome, e.cumulative_gas_used, e.logs);
+        if env_info.number >= params.l1_fee_transition {
+            receipt.l1_fee = Some(e.l1_fee);
+        }
+        receipt.deposit = t.is_enqueued() && env_info.number >= params.enqueued_tx_transition;
+        let receipt = TypedReceipt::new(t.tx_type(), receipt);
 
-        Ok(ApplyOutcome { receipt, output, trace: e.trace, vm_trace: e.vm_trace })
+        Ok(ApplyOutcome { receipt, output, trace: e.trace, vm_trace: e.vm_trace, fees })
     }
 
     // Execute a given transaction without committing changes.
@@ -1155,6 +1238,38 @@ impl<B: Backend> State<B> {
         Ok(pod_state::diff_pod(&pod_state_pre, &pod_state_post))
     }
 
+    /// Returns a `StateDiff` of the balances changed in the cache since the last commit.
+    /// Only balances are compared, nonce, code and storage are left out of the diff.
+    #[cfg(feature = "supply-check")]
+    pub fn balance_diff(&self) -> TrieResult<StateDiff> {
+        assert!(self.checkpoints.borrow().is_empty());
+        let db = &self.db.as_hash_db();
+        let db = self.factories.trie.readonly(db, &self.root)?;
+        let from_rlp = |b: &[u8]| Account::from_rlp(b).expect("decoding db value failed");
+
+        let mut raw = BTreeMap::new();
+        for (address, entry) in self.cache.borrow().iter() {
+            let pre = db.get_with(address.as_bytes(), from_rlp)?.map(|acc| *acc.balance());
+            let post = entry.account.as_ref().map(|acc| *acc.balance());
+            let balance = match (pre, post) {
+                (Some(pre), Some(post)) => Diff::new(pre, post),
+                (None, Some(post)) if !post.is_zero() => Diff::Born(post),
+                (Some(pre), None) if !pre.is_zero() => Diff::Died(pre),
+                _ => Diff::Same,
+            };
+            if !balance.is_same() {
+                let diff = AccountDiff {
+                    balance,
+                    nonce: Diff::Same,
+                    code: Diff::Same,
+                    storage: BTreeMap::new(),
+                };
+                raw.insert(*address, diff);
+            }
+        }
+        Ok(StateDiff { raw })
+    }
+
     /// Load required account data from the databases. Returns whether the cache succeeds.
     #[must_use]
     fn update_account_cache(
@@ -2586,6 +2701,24 @@ mod tests {
         assert_eq!(state.balance(&a).unwrap(), U256::from(0));
     }
 
+    #[test]
+    fn checkpoint_transient_storage() {
+        let mut state = get_temp_state();
+        let a = Address::zero();
+        let k = H256::from_low_u64_be(1);
+        state.set_transient_storage(&a, k, H256::from_low_u64_be(1));
+        state.checkpoint();
+        state.set_transient_storage(&a, k, H256::from_low_u64_be(2));
+        state.checkpoint();
+        state.set_transient_storage(&a, k, H256::from_low_u64_be(3));
+        state.discard_checkpoint();
+        assert_eq!(state.transient_storage_at(&a, &k), H256::from_low_u64_be(3));
+        state.revert_to_checkpoint();
+        assert_eq!(state.transient_storage_at(&a, &k), H256::from_low_u64_be(1));
+        state.begin_transaction();
+        assert_eq!(state.transient_storage_at(&a, &k), H256::zero());
+    }
+
     #[test]
     fn checkpoint_revert_to_get_storage_at() {
         let mut state = get_temp_state();
@@ -34,11 +34,14 @@ pub enum Error {
     StackUnderflow,
     /// When execution would exceed defined Stack Limit
     OutOfStack,
-    /// When there is not enough subroutine stack elements to return from
+    /// When there is not enough subroutine stack elements to return from. Retired with the
+    /// EIP-2315 subroutines, kept to decode existing traces.
     SubStackUnderflow,
-    /// When execution would exceed defined subroutine Stack Limit
+    /// When execution would exceed defined subroutine Stack Limit. Retired with the EIP-2315
+    /// subroutines, kept to decode existing traces.
     OutOfSubStack,
-    /// When the code walks into a subroutine, that is not allowed
+    /// When the code walks into a subroutine, that is not allowed. Retired with the EIP-2315
+    /// subroutines, kept to decode existing traces.
     InvalidSubEntry,
     /// When builtin contract failed on input data
     BuiltIn,
@@ -63,9 +66,6 @@ impl<'a> From<&'a VmError> for Error {
             VmError::BadInstruction { .. } => Error::BadInstruction,
             VmError::StackUnderflow { .. } => Error::StackUnderflow,
             VmError::OutOfStack { .. } => Error::OutOfStack,
-            VmError::SubStackUnderflow { .. } => Error::SubStackUnderflow,
-            VmError::OutOfSubStack { .. } => Error::OutOfSubStack,
-            VmError::InvalidSubEntry { .. } => Error::InvalidSubEntry,
             VmError::BuiltIn { .. } => Error::BuiltIn,
             VmError::InvalidCode => Error::InvalidCode,
             VmError::Internal(_) => Error::Internal,
@@ -169,4 +169,18 @@ mod tests {
         assert!(s.is_finished(), "List should be finished now");
         s.out();
     }
+
+    #[test]
+    fn retired_error_codes_roundtrip() {
+        for (err, code) in [
+            (Error::SubStackUnderflow, 11u8),
+            (Error::OutOfSubStack, 12),
+            (Error::InvalidSubEntry, 13),
+            (Error::InvalidCode, 14),
+        ] {
+            let encoded = rlp::encode(&err);
+            assert_eq!(encoded, rlp::encode(&code));
+            assert_eq!(rlp::decode::<Error>(&encoded).unwrap(), err);
+        }
+    }
 }
@@ -16,11 +16,11 @@
 
 //! Ethereum transaction
 
-use common_types::l2_cfg::INTRINSIC_GAS_FACTOR;
 use evm::Schedule;
-use types::l2_cfg::TX_BASE_SIZE;
 use types::transaction::{self, Action};
 
+use crate::fastlz;
+
 /// Extends transaction with gas verification method.
 pub trait Transaction {
     /// Get the transaction cost in gas for this transaction.
@@ -42,21 +42,46 @@ impl Transaction for transaction::Transaction {
 
 /// Get the transaction cost in gas for the given params.
 fn gas_required_for(is_create: bool, data: &[u8], schedule: &Schedule) -> u64 {
-    let mut intrinsic_gas_factor: usize = INTRINSIC_GAS_FACTOR;
-    let mut tx_base_size: usize = TX_BASE_SIZE;
-    #[cfg(any(test, feature = "test-helpers"))]
-    {
-        intrinsic_gas_factor = 1;
-        tx_base_size = 0;
+    let intrinsic_gas_factor = schedule.tx_data_gas_factor;
+    // EIP-3860 init code words, zero unless enabled in the schedule
+    let initcode_gas =
+        if is_create { (data.len() + 31) / 32 * schedule.initcode_word_gas } else { 0 };
+    let tx_gas = if is_create { schedule.tx_create_gas } else { schedule.tx_gas };
+    let base_size_gas =
+        schedule.tx_base_size * schedule.tx_data_non_zero_gas * intrinsic_gas_factor;
+    let base_gas = (base_size_gas + initcode_gas + tx_gas) as u64;
+    if schedule.tx_data_compressed {
+        // the compressed size is what ends up in a batch, each byte priced as non-zero
+        let compressed_len = fastlz::compress_len(data);
+        return base_gas
+            + (compressed_len * schedule.tx_data_non_zero_gas * intrinsic_gas_factor) as u64;
+    }
+    data.iter().fold(base_gas, |g, b| {
+        g + (match *b {
+            0 => schedule.tx_data_zero_gas * intrinsic_gas_factor,
+            _ => schedule.tx_data_non_zero_gas * intrinsic_gas_factor,
+        }) as u64
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn l2_intrinsic_gas() {
+        let mut schedule = Schedule::new_london();
+        schedule.tx_data_gas_factor = 100;
+        schedule.tx_base_size = 213;
+        let data = [0u8, 1, 0, 2];
+
+        let base = 213 * 16 * 100 + 21000;
+        assert_eq!(gas_required_for(false, &data, &schedule), base + 2 * 400 + 2 * 1600);
+        assert_eq!(gas_required_for(false, &[], &schedule), base);
+
+        // zero bytes compress well and are no longer priced one by one
+        schedule.tx_data_compressed = true;
+        assert_eq!(gas_required_for(false, &data, &schedule), base + 5 * 1600);
+        assert_eq!(gas_required_for(false, &[0u8; 1000], &schedule), base + 21 * 1600);
     }
-    data.iter().fold(
-        (tx_base_size * schedule.tx_data_non_zero_gas * intrinsic_gas_factor
-            + if is_create { schedule.tx_create_gas } else { schedule.tx_gas }) as u64,
-        |g, b| {
-            g + (match *b {
-                0 => schedule.tx_data_zero_gas * intrinsic_gas_factor,
-                _ => schedule.tx_data_non_zero_gas * intrinsic_gas_factor,
-            }) as u64
-        },
-    )
 }
@@ -20,7 +20,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::iter::FromIterator;
 
-use ethereum_types::{Address, Bloom, H256, U256};
+use ethereum_types::{Address, Bloom, H256, H64, U256};
 use parity_bytes::ToPretty;
 use rlp::{DecoderError, Encodable, Rlp, RlpStream};
 
@@ -456,15 +456,119 @@ impl ExtendedHeader {
     }
 }
 
+/// Seal of blocks produced by the L2 engine.
+///
+/// Encoded into the header seal as `[version, message_index, message_root, queue_index]`, each
+/// entry being a RLP item. Headers sealed before the versioned format carry the ethash styled
+/// `[mix_hash, nonce]` seal, which is still accepted by `Header::l2_seal`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct L2SealFields {
+    /// Index of the last cross layer message sent up to and including this block.
+    pub message_index: u64,
+    /// Message root emitted with the last cross layer message.
+    pub message_root: H256,
+    /// Number of enqueued transactions applied up to and including this block.
+    pub queue_index: u64,
+}
+
+impl L2SealFields {
+    /// Current seal layout version.
+    pub const VERSION: u8 = 1;
+    /// Number of seal fields of the current version.
+    pub const FIELDS: usize = 4;
+    /// Number of seal fields of the legacy `[mix_hash, nonce]` seal.
+    pub const LEGACY_FIELDS: usize = 2;
+
+    /// RLP encoded seal fields, as stored in the header.
+    pub fn seal_fields(&self) -> Vec<Bytes> {
+        vec![
+            rlp::encode(&Self::VERSION).to_vec(),
+            rlp::encode(&self.message_index).to_vec(),
+            rlp::encode(&self.message_root).to_vec(),
+            rlp::encode(&self.queue_index).to_vec(),
+        ]
+    }
+
+    /// RLP encoded legacy `[mix_hash, nonce]` seal fields. The queue index is not part of the
+    /// legacy seal, it is counted by the header difficulty.
+    pub fn legacy_seal_fields(&self) -> Vec<Bytes> {
+        vec![
+            rlp::encode(&self.message_root).to_vec(),
+            rlp::encode(&H64::from_low_u64_be(self.message_index)).to_vec(),
+        ]
+    }
+
+    /// Decode versioned seal fields.
+    pub fn decode(seal: &[Bytes]) -> Result<Self, DecoderError> {
+        if seal.len() != Self::FIELDS {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        let version: u8 = Rlp::new(&seal[0]).as_val()?;
+        if version != Self::VERSION {
+            return Err(DecoderError::Custom("Unknown L2 seal version"));
+        }
+        Ok(L2SealFields {
+            message_index: Rlp::new(&seal[1]).as_val()?,
+            message_root: Rlp::new(&seal[2]).as_val()?,
+            queue_index: Rlp::new(&seal[3]).as_val()?,
+        })
+    }
+}
+
+/// L1 data an L2 block was derived from.
+///
+/// Stored RLP encoded in the header extra data of L2 blocks.
+#[derive(Debug, Default, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct L1Origin {
+    /// Index of the batch within the rollup input.
+    pub batch_index: u64,
+    /// Hash of the rollup batch input posted to L1.
+    pub batch_input_hash: H256,
+    /// First queue index consumed by the batch.
+    pub queue_start: u64,
+    /// Queue index following the last one consumed by the batch.
+    pub queue_end: u64,
+    /// L1 timestamp of the batch.
+    pub l1_timestamp: u64,
+    /// Number of the L1 block the batch input was posted in.
+    pub l1_block_number: u64,
+    /// Base fee of the L1 block the batch input was posted in.
+    pub l1_base_fee: U256,
+}
+
+impl Header {
+    /// Decode the L1 origin recorded in the extra data of this header.
+    pub fn l1_origin(&self) -> Result<L1Origin, DecoderError> {
+        rlp::decode(&self.extra_data)
+    }
+
+    /// Decode the L2 seal of this header.
+    ///
+    /// Legacy `[mix_hash, nonce]` seals map to the message root and message index, the queue
+    /// index is taken from the difficulty which used to count the enqueued transactions.
+    pub fn l2_seal(&self) -> Result<L2SealFields, DecoderError> {
+        if self.seal.len() == L2SealFields::LEGACY_FIELDS {
+            let mix_hash: H256 = Rlp::new(&self.seal[0]).as_val()?;
+            let nonce: H64 = Rlp::new(&self.seal[1]).as_val()?;
+            return Ok(L2SealFields {
+                message_index: nonce.to_low_u64_be(),
+                message_root: mix_hash,
+                queue_index: self.difficulty.low_u64(),
+            });
+        }
+        L2SealFields::decode(&self.seal)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use ethereum_types::U256;
+    use ethereum_types::{H256, U256};
     use hex::FromHex;
     use rlp::{self, Rlp};
 
     use crate::BlockNumber;
 
-    use super::Header;
+    use super::{Header, L1Origin, L2SealFields};
 
     #[test]
     fn test_header_seal_fields() {
@@ -565,4 +669,75 @@ mod tests {
 
         assert_ne!(hash_legacy, hash_1559);
     }
+
+    #[test]
+    fn l2_seal_roundtrip() {
+        let fields = L2SealFields {
+            message_index: 7,
+            message_root: H256::repeat_byte(0x11),
+            queue_index: 42,
+        };
+        let mut header = Header::default();
+        header.set_seal(fields.seal_fields());
+        assert_eq!(header.seal().len(), L2SealFields::FIELDS);
+        assert_eq!(header.l2_seal().unwrap(), fields);
+
+        let mut seal = fields.seal_fields();
+        seal[0] = rlp::encode(&2u8).to_vec();
+        header.set_seal(seal);
+        assert!(header.l2_seal().is_err());
+    }
+
+    #[test]
+    fn l2_seal_from_legacy_seal() {
+        // that's rlp of block header created with ethash engine.
+        let header_rlp = "f901f9a0d405da4e66f1445d455195229624e133f5baafe72b5cf7b3c36c12c8146e98b7a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a05fb2b4bfdef7b314451cb138a534d225c922fc0e5fbe25e451142732c3e25c25a088d2ec6b9860aae1a2c3b299f72b6a5d70d7f7ba4722c78f2c49ba96273c2158a007c6fdfa8eea7e86b81f5b0fc0f78f90cc19f4aa60d323151e0cac660199e9a1b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302008003832fefba82524d84568e932a80a0a0349d8c3df71f1a48a9df7d03fd5f14aeee7d91332c009ecaff0a71ead405bd88ab4e252a7e8c2a23".from_hex().unwrap();
+        let rlp = Rlp::new(&header_rlp);
+        let header: Header =
+            Header::decode_rlp(&rlp, BlockNumber::max_value()).expect("error decoding header");
+
+        let fields = header.l2_seal().unwrap();
+        assert_eq!(fields.message_index, 0xab4e252a7e8c2a23);
+        assert_eq!(
+            fields.message_root,
+            H256::from_slice(
+                &"a0349d8c3df71f1a48a9df7d03fd5f14aeee7d91332c009ecaff0a71ead405bd"
+                    .from_hex::<Vec<u8>>()
+                    .unwrap()
+            )
+        );
+        assert_eq!(fields.queue_index, 0x020080);
+    }
+
+    #[test]
+    fn l2_seal_legacy_roundtrip() {
+        let fields = L2SealFields {
+            message_index: 7,
+            message_root: H256::repeat_byte(0x11),
+            queue_index: 42,
+        };
+        let mut header = Header::default();
+        header.set_difficulty(42.into());
+        header.set_seal(fields.legacy_seal_fields());
+        assert_eq!(header.seal().len(), 2);
+        assert_eq!(header.l2_seal().unwrap(), fields);
+    }
+
+    #[test]
+    fn l1_origin_roundtrip() {
+        let origin = L1Origin {
+            batch_index: 3,
+            batch_input_hash: H256::repeat_byte(0x22),
+            queue_start: 100,
+            queue_end: 104,
+            l1_timestamp: 1_670_000_000,
+            l1_block_number: 16_000_000,
+            l1_base_fee: U256::max_value(),
+        };
+        let mut header = Header::default();
+        header.set_extra_data(rlp::encode(&origin).to_vec());
+        assert!(header.extra_data().len() <= 0x80);
+        assert_eq!(header.l1_origin().unwrap(), origin);
+        assert!(Header::default().l1_origin().is_err());
+    }
 }
@@ -15,8 +15,20 @@ pub const L1_CROSS_LAYER_WITNESS: Address = H160([
     0x7E, 0x5F, 0x45, 0x52, 0x09, 0x1A, 0x69, 0x12, 0x5d, 0x5D, 0xfC, 0xb7, 0xb8, 0xC2, 0x65, 0x90,
     0x29, 0x39, 0x5B, 0xdf,
 ]);
+pub const L2_L1_ATTRIBUTES: Address =
+    H160([0x22, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x01]);
+pub const L2_DEPLOYER_ALLOWLIST: Address =
+    H160([0x22, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x03]);
+pub const L2_SEQUENCER_REGISTRY: Address =
+    H160([0x22, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x04]);
 pub const L2_FEE_COLLECTOR: Address =
     H160([0xfe, 0xe0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x0f, 0xee]);
+pub const L2_L1_FEE_ORACLE: Address =
+    H160([0x22, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+pub const L2_L1_FEE_VAULT: Address =
+    H160([0xfe, 0xe0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x1f, 0xee]);
 
 pub const L2_BLOCK_MAX_GAS_LIMIT: u32 = 0x3938700;
 pub const L2_BLOCK_MIN_GAS_LIMIT: u32 = 0x3938700;
+// gas of a block available to enqueued transactions, the rest is kept for L2 transactions
+pub const L2_ENQUEUED_GAS_LIMIT: u32 = 0x1c9c380;
@@ -47,6 +47,15 @@ pub struct LegacyReceipt {
     pub logs: Vec<LogEntry>,
     /// Transaction outcome.
     pub outcome: TransactionOutcome,
+    /// L1 data fee charged to the sender, present once L1 fees are enabled.
+    ///
+    /// Encoded as a trailing item, only together with a known outcome. A deposit without fee
+    /// encodes it as an empty list to keep its marker in place.
+    pub l1_fee: Option<U256>,
+    /// Whether the transaction was enqueued on L1, with its gas prepaid there.
+    ///
+    /// Encoded as a trailing item after the L1 data fee, only together with a known outcome.
+    pub deposit: bool,
 }
 
 impl LegacyReceipt {
@@ -59,6 +68,8 @@ impl LegacyReceipt {
             }),
             logs,
             outcome,
+            l1_fee: None,
+            deposit: false,
         }
     }
     pub fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
@@ -68,8 +79,10 @@ impl LegacyReceipt {
                 gas_used: rlp.val_at(0)?,
                 log_bloom: rlp.val_at(1)?,
                 logs: rlp.list_at(2)?,
+                l1_fee: None,
+                deposit: false,
             }),
-            4 => Ok(LegacyReceipt {
+            count @ 4..=6 => Ok(LegacyReceipt {
                 gas_used: rlp.val_at(1)?,
                 log_bloom: rlp.val_at(2)?,
                 logs: rlp.list_at(3)?,
@@ -81,28 +94,60 @@ impl LegacyReceipt {
                         TransactionOutcome::StateRoot(first.as_val()?)
                     }
                 },
+                l1_fee: if count >= 5 { Self::decode_l1_fee(&rlp.at(4)?)? } else { None },
+                deposit: if count == 6 { rlp.val_at(5)? } else { false },
             }),
             _ => Err(DecoderError::RlpIncorrectListLen),
         }
     }
 
+    fn decode_l1_fee(rlp: &Rlp) -> Result<Option<U256>, DecoderError> {
+        if rlp.is_list() {
+            // placeholder of a deposit without fee
+            return match rlp.item_count()? {
+                0 => Ok(None),
+                _ => Err(DecoderError::RlpExpectedToBeData),
+            };
+        }
+        Ok(Some(rlp.as_val()?))
+    }
+
     pub fn rlp_append(&self, s: &mut RlpStream) {
+        let deposit = self.deposit && self.outcome != TransactionOutcome::Unknown;
+        let l1_fee = match self.outcome {
+            TransactionOutcome::Unknown => None,
+            _ => self.l1_fee,
+        };
+        let fields = 4 + (l1_fee.is_some() || deposit) as usize + deposit as usize;
         match self.outcome {
             TransactionOutcome::Unknown => {
                 s.begin_list(3);
             }
             TransactionOutcome::StateRoot(ref root) => {
-                s.begin_list(4);
+                s.begin_list(fields);
                 s.append(root);
             }
             TransactionOutcome::StatusCode(ref status_code) => {
-                s.begin_list(4);
+                s.begin_list(fields);
                 s.append(status_code);
             }
         }
         s.append(&self.gas_used);
         s.append(&self.log_bloom);
         s.append_list(&self.logs);
+        match l1_fee {
+            Some(l1_fee) => {
+                s.append(&l1_fee);
+            }
+            // a deposit always carries the fee item ahead of its marker
+            None if deposit => {
+                s.begin_list(0);
+            }
+            None => {}
+        }
+        if deposit {
+            s.append(&deposit);
+        }
     }
 }
 
@@ -111,6 +156,7 @@ pub enum TypedReceipt {
     Legacy(LegacyReceipt),
     AccessList(LegacyReceipt),
     EIP1559Transaction(LegacyReceipt),
+    EIP7702Transaction(LegacyReceipt),
 }
 
 impl TypedReceipt {
@@ -118,6 +164,7 @@ impl TypedReceipt {
     pub fn new(type_id: TypedTxId, legacy_receipt: LegacyReceipt) -> Self {
         //curently we are using same receipt for both legacy and typed transaction
         match type_id {
+            TypedTxId::EIP7702Transaction => Self::EIP7702Transaction(legacy_receipt),
             TypedTxId::EIP1559Transaction => Self::EIP1559Transaction(legacy_receipt),
             TypedTxId::AccessList => Self::AccessList(legacy_receipt),
             TypedTxId::Legacy => Self::Legacy(legacy_receipt),
@@ -129,6 +176,7 @@ impl TypedReceipt {
             Self::Legacy(_) => TypedTxId::Legacy,
             Self::AccessList(_) => TypedTxId::AccessList,
             Self::EIP1559Transaction(_) => TypedTxId::EIP1559Transaction,
+            Self::EIP7702Transaction(_) => TypedTxId::EIP7702Transaction,
         }
     }
 
@@ -137,6 +185,7 @@ impl TypedReceipt {
             Self::Legacy(receipt) => receipt,
             Self::AccessList(receipt) => receipt,
             Self::EIP1559Transaction(receipt) => receipt,
+            Self::EIP7702Transaction(receipt) => receipt,
         }
     }
 
@@ -145,6 +194,7 @@ impl TypedReceipt {
             Self::Legacy(receipt) => receipt,
             Self::AccessList(receipt) => receipt,
             Self::EIP1559Transaction(receipt) => receipt,
+            Self::EIP7702Transaction(receipt) => receipt,
         }
     }
 
@@ -159,6 +209,10 @@ impl TypedReceipt {
         }
         //other transaction types
         match id.unwrap() {
+            TypedTxId::EIP7702Transaction => {
+                let rlp = Rlp::new(&tx[1..]);
+                Ok(Self::EIP7702Transaction(LegacyReceipt::decode(&rlp)?))
+            }
             TypedTxId::EIP1559Transaction => {
                 let rlp = Rlp::new(&tx[1..]);
                 Ok(Self::EIP1559Transaction(LegacyReceipt::decode(&rlp)?))
@@ -205,6 +259,11 @@ impl TypedReceipt {
                 receipt.rlp_append(&mut rlps);
                 s.append(&[&[TypedTxId::EIP1559Transaction as u8], rlps.as_raw()].concat());
             }
+            Self::EIP7702Transaction(receipt) => {
+                let mut rlps = RlpStream::new();
+                receipt.rlp_append(&mut rlps);
+                s.append(&[&[TypedTxId::EIP7702Transaction as u8], rlps.as_raw()].concat());
+            }
         }
     }
 
@@ -232,6 +291,11 @@ impl TypedReceipt {
                 receipt.rlp_append(&mut rlps);
                 [&[TypedTxId::EIP1559Transaction as u8], rlps.as_raw()].concat()
             }
+            Self::EIP7702Transaction(receipt) => {
+                let mut rlps = RlpStream::new();
+                receipt.rlp_append(&mut rlps);
+                [&[TypedTxId::EIP7702Transaction as u8], rlps.as_raw()].concat()
+            }
         }
     }
 }
@@ -320,7 +384,8 @@ pub struct LocalizedReceipt {
 mod tests {
     use super::{LegacyReceipt, TransactionOutcome, TypedReceipt, TypedTxId};
     use crate::log_entry::LogEntry;
-    use ethereum_types::{H160, H256};
+    use ethereum_types::{H160, H256, U256};
+    use rlp::Rlp;
     use std::str::FromStr;
 
     #[test]
@@ -439,4 +504,39 @@ mod tests {
         let decoded = TypedReceipt::decode(&encoded).expect("decoding receipt failed");
         assert_eq!(decoded, r);
     }
+
+    #[test]
+    fn test_status_code_with_l1_fee() {
+        let mut receipt =
+            LegacyReceipt::new(TransactionOutcome::StatusCode(1), 0x5208.into(), vec![]);
+        receipt.l1_fee = Some(U256::from(0x1234));
+        let r = TypedReceipt::new(TypedTxId::EIP1559Transaction, receipt);
+
+        let encoded = r.encode();
+        assert_eq!(Rlp::new(&encoded[1..]).item_count().unwrap(), 5);
+        let decoded = TypedReceipt::decode(&encoded).expect("decoding receipt failed");
+        assert_eq!(decoded, r);
+
+        // receipts without a known outcome leave the fee out
+        let mut receipt = LegacyReceipt::new(TransactionOutcome::Unknown, 0x5208.into(), vec![]);
+        receipt.l1_fee = Some(U256::from(0x1234));
+        let r = TypedReceipt::new(TypedTxId::Legacy, receipt);
+        assert_eq!(Rlp::new(&r.encode()).item_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_status_code_deposit() {
+        for l1_fee in [None, Some(U256::zero()), Some(U256::from(0x1234))].iter() {
+            let mut receipt =
+                LegacyReceipt::new(TransactionOutcome::StatusCode(1), 0x5208.into(), vec![]);
+            receipt.l1_fee = *l1_fee;
+            receipt.deposit = true;
+            let r = TypedReceipt::new(TypedTxId::Legacy, receipt);
+
+            let encoded = r.encode();
+            assert_eq!(Rlp::new(&encoded).item_count().unwrap(), 6);
+            let decoded = TypedReceipt::decode(&encoded).expect("decoding receipt failed");
+            assert_eq!(decoded, r);
+        }
+    }
 }
@@ -32,12 +32,16 @@ use rlp::{self, DecoderError, Rlp, RlpStream};
 
 pub type AccessListItem = (H160, Vec<H256>);
 pub type AccessList = Vec<AccessListItem>;
+pub type AuthorizationList = Vec<Authorization>;
 
 use super::TypedTxId;
 
 type Bytes = Vec<u8>;
 type BlockNumber = u64;
 
+/// Prefix of the message signed by an EIP-7702 authorization.
+pub const EIP7702_AUTHORIZATION_MAGIC: u8 = 0x05;
+
 /// Fake address for unsigned transactions as defined by EIP-86.
 pub const UNSIGNED_SENDER: Address = H160([0xff; 20]);
 
@@ -122,6 +126,89 @@ pub mod signature {
     }
 }
 
+/// EIP-7702 authorization: the `authority` recovered from the signature allows its account
+/// code to be set to a delegation designator pointing at `address`.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Authorization {
+    /// Chain the authorization is valid for, zero for any chain.
+    pub chain_id: U256,
+    /// Address the authority delegates its code to.
+    pub address: Address,
+    /// Expected nonce of the authority.
+    pub nonce: u64,
+    /// Parity of the signature's y coordinate.
+    pub y_parity: u8,
+    /// The R field of the signature.
+    pub r: U256,
+    /// The S field of the signature.
+    pub s: U256,
+}
+
+impl Authorization {
+    /// Signs an authorization with the authority's secret.
+    #[cfg(feature = "std")]
+    pub fn sign(secret: &Secret, chain_id: U256, address: Address, nonce: u64) -> Authorization {
+        let mut authorization = Authorization { chain_id, address, nonce, ..Default::default() };
+        let sig = publickey::sign(secret, &authorization.signature_hash())
+            .expect("data is valid and context has signing capabilities; qed");
+        authorization.y_parity = sig.v();
+        authorization.r = sig.r().into();
+        authorization.s = sig.s().into();
+        authorization
+    }
+
+    /// The message hash: `keccak(0x05 || rlp([chain_id, address, nonce]))`.
+    pub fn signature_hash(&self) -> H256 {
+        let mut stream = RlpStream::new_list(3);
+        stream.append(&self.chain_id);
+        stream.append(&self.address);
+        stream.append(&self.nonce);
+        keccak([&[EIP7702_AUTHORIZATION_MAGIC], stream.as_raw()].concat())
+    }
+
+    /// Recovers the authority. Returns `None` for an invalid or high 's' signature.
+    pub fn authority(&self) -> Option<Address> {
+        if self.y_parity > 1 {
+            return None;
+        }
+        let r: H256 = BigEndianHash::from_uint(&self.r);
+        let s: H256 = BigEndianHash::from_uint(&self.s);
+        let signature = Signature::from_rsv(&r, &s, self.y_parity);
+        if !signature.is_low_s() {
+            return None;
+        }
+        recover(&signature, &self.signature_hash())
+    }
+}
+
+impl rlp::Decodable for Authorization {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 6 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Authorization {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+            y_parity: rlp.val_at(3)?,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+impl rlp::Encodable for Authorization {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(6);
+        s.append(&self.chain_id);
+        s.append(&self.address);
+        s.append(&self.nonce);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
 /// A set of information describing an externally-originating message call
 /// or contract creation operation.
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -472,6 +559,154 @@ impl EIP1559TransactionTx {
     }
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EIP7702TransactionTx {
+    pub transaction: EIP1559TransactionTx,
+    pub authorization_list: AuthorizationList,
+}
+
+impl EIP7702TransactionTx {
+    pub fn tx_type(&self) -> TypedTxId {
+        TypedTxId::EIP7702Transaction
+    }
+
+    pub fn tx(&self) -> &Transaction {
+        self.transaction.tx()
+    }
+
+    pub fn tx_mut(&mut self) -> &mut Transaction {
+        self.transaction.tx_mut()
+    }
+
+    // decode bytes by this payload spec: rlp([4, [chainId, nonce, maxPriorityFeePerGas, maxFeePerGas(gasPrice), gasLimit, to, value, data, access_list, authorization_list, senderV, senderR, senderS]])
+    pub fn decode(tx: &[u8]) -> Result<UnverifiedTransaction, DecoderError> {
+        let tx_rlp = &Rlp::new(tx);
+
+        // we need to have 13 items in this list
+        if tx_rlp.item_count()? != 13 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        let chain_id = Some(tx_rlp.val_at(0)?);
+
+        let max_priority_fee_per_gas = tx_rlp.val_at(2)?;
+
+        let tx = Transaction {
+            nonce: tx_rlp.val_at(1)?,
+            gas_price: tx_rlp.val_at(3)?, //taken from max_fee_per_gas
+            gas: tx_rlp.val_at(4)?,
+            action: tx_rlp.val_at(5)?,
+            value: tx_rlp.val_at(6)?,
+            data: tx_rlp.val_at(7)?,
+        };
+
+        // set-code transactions can not create contracts
+        if tx.action == Action::Create {
+            return Err(DecoderError::Custom("EIP-7702 transaction without destination"));
+        }
+
+        // access list we get from here
+        let accl_rlp = tx_rlp.at(8)?;
+
+        // access_list pattern: [[{20 bytes}, [{32 bytes}...]]...]
+        let mut accl: AccessList = Vec::new();
+
+        for i in 0..accl_rlp.item_count()? {
+            let accounts = accl_rlp.at(i)?;
+
+            // check if there is list of 2 items
+            if accounts.item_count()? != 2 {
+                return Err(DecoderError::Custom("Unknown access list length"));
+            }
+            accl.push((accounts.val_at(0)?, accounts.list_at(1)?));
+        }
+
+        // authorization_list pattern: [[chain_id, address, nonce, y_parity, r, s]...]
+        let authorization_list: AuthorizationList = tx_rlp.list_at(9)?;
+        if authorization_list.is_empty() {
+            return Err(DecoderError::Custom("Empty authorization list"));
+        }
+
+        // we get signature part from here
+        let signature = SignatureComponents {
+            standard_v: tx_rlp.val_at(10)?,
+            r: tx_rlp.val_at(11)?,
+            s: tx_rlp.val_at(12)?,
+        };
+
+        // and here we create UnverifiedTransaction and calculate its hash
+        Ok(UnverifiedTransaction::new(
+            TypedTransaction::EIP7702Transaction(EIP7702TransactionTx {
+                transaction: EIP1559TransactionTx {
+                    transaction: AccessListTx::new(tx, accl),
+                    max_priority_fee_per_gas,
+                },
+                authorization_list,
+            }),
+            chain_id,
+            signature,
+            H256::zero(),
+        )
+        .compute_hash())
+    }
+
+    fn encode_payload(
+        &self, chain_id: Option<u64>, signature: Option<&SignatureComponents>,
+    ) -> RlpStream {
+        let mut stream = RlpStream::new();
+
+        let list_size = if signature.is_some() { 13 } else { 10 };
+        stream.begin_list(list_size);
+
+        // append chain_id. from EIP-2930: chainId is defined to be an integer of arbitrary size.
+        stream.append(&(if let Some(n) = chain_id { n } else { 0 }));
+
+        stream.append(&self.tx().nonce);
+        stream.append(&self.transaction.max_priority_fee_per_gas);
+        stream.append(&self.tx().gas_price);
+        stream.append(&self.tx().gas);
+        stream.append(&self.tx().action);
+        stream.append(&self.tx().value);
+        stream.append(&self.tx().data);
+
+        // access list
+        let access_list = &self.transaction.transaction.access_list;
+        stream.begin_list(access_list.len());
+        for access in access_list.iter() {
+            stream.begin_list(2);
+            stream.append(&access.0);
+            stream.begin_list(access.1.len());
+            for storage_key in access.1.iter() {
+                stream.append(storage_key);
+            }
+        }
+
+        // authorization list
+        stream.append_list(&self.authorization_list);
+
+        // append signature if any
+        if let Some(signature) = signature {
+            signature.rlp_append(&mut stream);
+        }
+        stream
+    }
+
+    // encode by this payload spec: 0x04 | rlp([4, [chainId, nonce, maxPriorityFeePerGas, maxFeePerGas(gasPrice), gasLimit, to, value, data, access_list, authorization_list, senderV, senderR, senderS]])
+    pub fn encode(
+        &self, chain_id: Option<u64>, signature: Option<&SignatureComponents>,
+    ) -> Vec<u8> {
+        let stream = self.encode_payload(chain_id, signature);
+        // make as vector of bytes
+        [&[TypedTxId::EIP7702Transaction as u8], stream.as_raw()].concat()
+    }
+
+    pub fn rlp_append(
+        &self, rlp: &mut RlpStream, chain_id: Option<u64>, signature: &SignatureComponents,
+    ) {
+        rlp.append(&self.encode(chain_id, Some(signature)));
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum TypedTransaction {
     Legacy(Transaction),
@@ -480,6 +715,8 @@ pub enum TypedTransaction {
     // EIP-2930 Transaction with a list of addresses and storage keys that the transaction plans to access.
     // Accesses outside the list are possible, but become more expensive.
     EIP1559Transaction(EIP1559TransactionTx),
+    // EIP-7702 Transaction carrying authorizations that set delegation code on their signers.
+    EIP7702Transaction(EIP7702TransactionTx),
 }
 
 impl TypedTransaction {
@@ -488,6 +725,7 @@ impl TypedTransaction {
             Self::Legacy(_) => TypedTxId::Legacy,
             Self::AccessList(_) => TypedTxId::AccessList,
             Self::EIP1559Transaction(_) => TypedTxId::EIP1559Transaction,
+            Self::EIP7702Transaction(_) => TypedTxId::EIP7702Transaction,
         }
     }
 
@@ -497,6 +735,7 @@ impl TypedTransaction {
             Self::Legacy(tx) => tx.encode(chain_id, None),
             Self::AccessList(tx) => tx.encode(chain_id, None),
             Self::EIP1559Transaction(tx) => tx.encode(chain_id, None),
+            Self::EIP7702Transaction(tx) => tx.encode(chain_id, None),
         })
     }
 
@@ -573,6 +812,7 @@ impl TypedTransaction {
             Self::Legacy(tx) => tx,
             Self::AccessList(ocl) => ocl.tx(),
             Self::EIP1559Transaction(tx) => tx.tx(),
+            Self::EIP7702Transaction(tx) => tx.tx(),
         }
     }
 
@@ -581,22 +821,32 @@ impl TypedTransaction {
             Self::Legacy(tx) => tx,
             Self::AccessList(ocl) => ocl.tx_mut(),
             Self::EIP1559Transaction(tx) => tx.tx_mut(),
+            Self::EIP7702Transaction(tx) => tx.tx_mut(),
         }
     }
 
     pub fn access_list(&self) -> Option<&AccessList> {
         match self {
+            Self::EIP7702Transaction(tx) => Some(&tx.transaction.transaction.access_list),
             Self::EIP1559Transaction(tx) => Some(&tx.transaction.access_list),
             Self::AccessList(tx) => Some(&tx.access_list),
             Self::Legacy(_) => None,
         }
     }
 
+    pub fn authorization_list(&self) -> Option<&AuthorizationList> {
+        match self {
+            Self::EIP7702Transaction(tx) => Some(&tx.authorization_list),
+            _ => None,
+        }
+    }
+
     pub fn effective_gas_price(&self, block_base_fee: Option<U256>) -> U256 {
         match self {
-            Self::EIP1559Transaction(tx) => {
-                let (v2, overflow) =
-                    tx.max_priority_fee_per_gas.overflowing_add(block_base_fee.unwrap_or_default());
+            Self::EIP1559Transaction(_) | Self::EIP7702Transaction(_) => {
+                let (v2, overflow) = self
+                    .max_priority_fee_per_gas()
+                    .overflowing_add(block_base_fee.unwrap_or_default());
                 if overflow {
                     self.tx().gas_price
                 } else {
@@ -611,6 +861,7 @@ impl TypedTransaction {
     pub fn max_priority_fee_per_gas(&self) -> U256 {
         match self {
             Self::EIP1559Transaction(tx) => tx.max_priority_fee_per_gas,
+            Self::EIP7702Transaction(tx) => tx.transaction.max_priority_fee_per_gas,
             Self::AccessList(tx) => tx.tx().gas_price,
             Self::Legacy(tx) => tx.gas_price,
         }
@@ -627,6 +878,9 @@ impl TypedTransaction {
             Self::EIP1559Transaction(tx) => {
                 tx.tx().gas_price.is_zero() && tx.max_priority_fee_per_gas.is_zero()
             }
+            Self::EIP7702Transaction(tx) => {
+                tx.tx().gas_price.is_zero() && tx.transaction.max_priority_fee_per_gas.is_zero()
+            }
             Self::AccessList(tx) => tx.tx().gas_price.is_zero(),
             Self::Legacy(tx) => tx.gas_price.is_zero(),
         }
@@ -643,6 +897,7 @@ impl TypedTransaction {
         }
         // other transaction types
         match id.unwrap() {
+            TypedTxId::EIP7702Transaction => EIP7702TransactionTx::decode(&tx[1..]),
             TypedTxId::EIP1559Transaction => EIP1559TransactionTx::decode(&tx[1..]),
             TypedTxId::AccessList => AccessListTx::decode(&tx[1..]),
             TypedTxId::Legacy => return Err(DecoderError::Custom("Unknown transaction legacy")),
@@ -704,6 +959,7 @@ impl TypedTransaction {
             Self::Legacy(tx) => tx.rlp_append(s, chain_id, signature),
             Self::AccessList(opt) => opt.rlp_append(s, chain_id, signature),
             Self::EIP1559Transaction(tx) => tx.rlp_append(s, chain_id, signature),
+            Self::EIP7702Transaction(tx) => tx.rlp_append(s, chain_id, signature),
         }
     }
 
@@ -720,6 +976,7 @@ impl TypedTransaction {
             Self::Legacy(tx) => tx.encode(chain_id, signature),
             Self::AccessList(opt) => opt.encode(chain_id, signature),
             Self::EIP1559Transaction(tx) => tx.encode(chain_id, signature),
+            Self::EIP7702Transaction(tx) => tx.encode(chain_id, signature),
         }
     }
 }
@@ -1200,6 +1457,85 @@ mod tests {
         }
     }
 
+    fn eip7702_tx(authorization_list: AuthorizationList, action: Action) -> TypedTransaction {
+        TypedTransaction::EIP7702Transaction(EIP7702TransactionTx {
+            transaction: EIP1559TransactionTx {
+                transaction: AccessListTx::new(
+                    Transaction {
+                        action,
+                        nonce: U256::from(42u32),
+                        gas_price: U256::from(3000u32),
+                        gas: U256::from(80_000u32),
+                        value: U256::from(1u32),
+                        data: b"Hello!".to_vec(),
+                    },
+                    vec![(H160::from_low_u64_be(400), vec![H256::from_low_u64_be(102)])],
+                ),
+                max_priority_fee_per_gas: U256::from(100u32),
+            },
+            authorization_list,
+        })
+    }
+
+    #[test]
+    fn should_encode_decode_eip7702_tx() {
+        let key = Random.generate();
+        let authority = Random.generate();
+        let authorization =
+            Authorization::sign(authority.secret(), 69.into(), H160::from_low_u64_be(0xc0de), 7);
+        let t = eip7702_tx(vec![authorization], Action::Call(H160::from_low_u64_be(10)))
+            .sign(&key.secret(), Some(69));
+        let encoded = t.encode();
+        assert_eq!(encoded[0], TypedTxId::EIP7702Transaction as u8);
+
+        let t_new =
+            TypedTransaction::decode(&encoded).expect("Error on UnverifiedTransaction decoder");
+        assert_eq!(t_new.unsigned, t.unsigned);
+        assert_eq!(t_new.hash(), t.hash());
+        assert_eq!(t_new.recover_sender(), Some(t.sender()));
+
+        let authorization = &t_new.authorization_list().unwrap()[0];
+        assert_eq!(
+            authorization.authority(),
+            Some(publickey::public_to_address(authority.public()))
+        );
+    }
+
+    #[test]
+    fn should_reject_eip7702_tx_without_destination_or_authorizations() {
+        let key = Random.generate();
+        let authorization =
+            Authorization::sign(key.secret(), 0.into(), H160::from_low_u64_be(0xc0de), 0);
+
+        let create = eip7702_tx(vec![authorization], Action::Create).sign(&key.secret(), Some(69));
+        assert!(TypedTransaction::decode(&create.encode()).is_err());
+
+        let empty = eip7702_tx(vec![], Action::Call(H160::from_low_u64_be(10)))
+            .sign(&key.secret(), Some(69));
+        assert!(TypedTransaction::decode(&empty.encode()).is_err());
+    }
+
+    #[test]
+    fn should_reject_high_s_authorization() {
+        let key = Random.generate();
+        let mut authorization =
+            Authorization::sign(key.secret(), 0.into(), H160::from_low_u64_be(0xc0de), 0);
+        assert!(authorization.authority().is_some());
+
+        // secp256k1n - s recovers the same key with the other parity
+        let n = U256::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        authorization.s = n - authorization.s;
+        authorization.y_parity ^= 1;
+        assert_eq!(authorization.authority(), None);
+
+        authorization.y_parity = 2;
+        assert_eq!(authorization.authority(), None);
+    }
+
     #[test]
     fn should_decode_access_list_in_rlp() {
         use hex::FromHex;
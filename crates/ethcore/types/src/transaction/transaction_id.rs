@@ -21,6 +21,7 @@ use ethereum_types::U64;
 #[derive(Eq, Hash, Debug, Copy, Clone, PartialEq)]
 #[repr(u8)]
 pub enum TypedTxId {
+    EIP7702Transaction = 0x04,
     EIP1559Transaction = 0x02,
     AccessList = 0x01,
     Legacy = 0x00,
@@ -33,12 +34,14 @@ impl TypedTxId {
             0 => Some(Self::Legacy),
             1 => Some(Self::AccessList),
             2 => Some(Self::EIP1559Transaction),
+            4 => Some(Self::EIP7702Transaction),
             _ => None,
         }
     }
 
     pub fn try_from_wire_byte(n: u8) -> Result<Self, ()> {
         match n {
+            x if x == TypedTxId::EIP7702Transaction as u8 => Ok(TypedTxId::EIP7702Transaction),
             x if x == TypedTxId::EIP1559Transaction as u8 => Ok(TypedTxId::EIP1559Transaction),
             x if x == TypedTxId::AccessList as u8 => Ok(TypedTxId::AccessList),
             x if (x & 0x80) != 0x00 => Ok(TypedTxId::Legacy),
@@ -53,6 +56,7 @@ impl TypedTxId {
             Some(0x00) => Some(Self::Legacy),
             Some(0x01) => Some(Self::AccessList),
             Some(0x02) => Some(Self::EIP1559Transaction),
+            Some(0x04) => Some(Self::EIP7702Transaction),
             _ => None,
         }
     }
@@ -75,6 +79,7 @@ mod tests {
 
     #[test]
     fn typed_tx_id_try_from_wire() {
+        assert_eq!(Ok(TypedTxId::EIP7702Transaction), TypedTxId::try_from_wire_byte(0x04));
         assert_eq!(Ok(TypedTxId::EIP1559Transaction), TypedTxId::try_from_wire_byte(0x02));
         assert_eq!(Ok(TypedTxId::AccessList), TypedTxId::try_from_wire_byte(0x01));
         assert_eq!(Ok(TypedTxId::Legacy), TypedTxId::try_from_wire_byte(0x81));
@@ -87,6 +92,7 @@ mod tests {
         assert_eq!(Some(U64::from(0x00)), TypedTxId::Legacy.to_U64_option_id());
         assert_eq!(Some(U64::from(0x01)), TypedTxId::AccessList.to_U64_option_id());
         assert_eq!(Some(U64::from(0x02)), TypedTxId::EIP1559Transaction.to_U64_option_id());
+        assert_eq!(Some(U64::from(0x04)), TypedTxId::EIP7702Transaction.to_U64_option_id());
     }
 
     #[test]
@@ -100,6 +106,10 @@ mod tests {
             Some(TypedTxId::EIP1559Transaction),
             TypedTxId::from_U64_option_id(Some(U64::from(0x02)))
         );
+        assert_eq!(
+            Some(TypedTxId::EIP7702Transaction),
+            TypedTxId::from_U64_option_id(Some(U64::from(0x04)))
+        );
         assert_eq!(None, TypedTxId::from_U64_option_id(Some(U64::from(0x03))));
     }
 
@@ -108,6 +118,7 @@ mod tests {
         assert_eq!(Some(TypedTxId::Legacy), TypedTxId::from_u8_id(0));
         assert_eq!(Some(TypedTxId::AccessList), TypedTxId::from_u8_id(1));
         assert_eq!(Some(TypedTxId::EIP1559Transaction), TypedTxId::from_u8_id(2));
+        assert_eq!(Some(TypedTxId::EIP7702Transaction), TypedTxId::from_u8_id(4));
         assert_eq!(None, TypedTxId::from_u8_id(3));
     }
 }
@@ -28,6 +28,8 @@ use rlp::Rlp;
 
 /// View onto transaction rlp. Assumption is this is part of block.
 /// Typed Transaction View. It handles raw bytes to search for particular field.
+/// EIP7702 tx:
+/// 4 | [chainId, nonce, maxPriorityFeePerGas, maxFeePerGas(gasPrice), gasLimit, to, value, data, access_list, authorization_list, senderV, senderR, senderS]
 /// EIP1559 tx:
 /// 2 | [chainId, nonce, maxPriorityFeePerGas, maxFeePerGas(gasPrice), gasLimit, to, value, data, access_list, senderV, senderR, senderS]
 /// Access tx:
@@ -79,7 +81,7 @@ impl<'a> TypedTransactionView<'a> {
                 signature::extract_chain_id_from_legacy_v(self.rlp.val_at(6)).unwrap_or(0)
             }
             TypedTxId::AccessList => view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(0),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(0)
             }
         }
@@ -90,7 +92,7 @@ impl<'a> TypedTransactionView<'a> {
         match self.transaction_type {
             TypedTxId::Legacy => self.rlp.val_at(0),
             TypedTxId::AccessList => view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(1),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(1)
             }
         }
@@ -101,7 +103,7 @@ impl<'a> TypedTransactionView<'a> {
         match self.transaction_type {
             TypedTxId::Legacy => self.rlp.val_at(1),
             TypedTxId::AccessList => view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(2),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(3)
             }
         }
@@ -112,7 +114,7 @@ impl<'a> TypedTransactionView<'a> {
         match self.transaction_type {
             TypedTxId::Legacy => self.gas_price(),
             TypedTxId::AccessList => self.gas_price(),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 let max_priority_fee_per_gas: U256 =
                     view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(2);
 
@@ -130,7 +132,7 @@ impl<'a> TypedTransactionView<'a> {
             TypedTxId::AccessList => {
                 self.gas_price().saturating_sub(block_base_fee.unwrap_or_default())
             }
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 let max_priority_fee_per_gas: U256 =
                     view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(2);
                 min(
@@ -146,7 +148,7 @@ impl<'a> TypedTransactionView<'a> {
         match self.transaction_type {
             TypedTxId::Legacy => self.rlp.val_at(2),
             TypedTxId::AccessList => view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(3),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(4)
             }
         }
@@ -157,7 +159,7 @@ impl<'a> TypedTransactionView<'a> {
         match self.transaction_type {
             TypedTxId::Legacy => self.rlp.val_at(4),
             TypedTxId::AccessList => view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(5),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(6)
             }
         }
@@ -168,7 +170,7 @@ impl<'a> TypedTransactionView<'a> {
         match self.transaction_type {
             TypedTxId::Legacy => self.rlp.val_at(5),
             TypedTxId::AccessList => view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(6),
-            TypedTxId::EIP1559Transaction => {
+            TypedTxId::EIP1559Transaction | TypedTxId::EIP7702Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(7)
             }
         }
@@ -198,6 +200,16 @@ impl<'a> TypedTransactionView<'a> {
                     chain_id,
                 )
             }
+            TypedTxId::EIP7702Transaction => {
+                let chain_id = match self.chain_id() {
+                    0 => None,
+                    n => Some(n),
+                };
+                signature::add_chain_replay_protection(
+                    view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(10),
+                    chain_id,
+                )
+            }
         };
         r as u8
     }
@@ -209,6 +221,9 @@ impl<'a> TypedTransactionView<'a> {
             TypedTxId::EIP1559Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(9)
             }
+            TypedTxId::EIP7702Transaction => {
+                view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(10)
+            }
         }
     }
 
@@ -220,6 +235,9 @@ impl<'a> TypedTransactionView<'a> {
             TypedTxId::EIP1559Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(10)
             }
+            TypedTxId::EIP7702Transaction => {
+                view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(11)
+            }
         }
     }
 
@@ -231,6 +249,9 @@ impl<'a> TypedTransactionView<'a> {
             TypedTxId::EIP1559Transaction => {
                 view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(11)
             }
+            TypedTxId::EIP7702Transaction => {
+                view!(Self, &self.rlp.rlp.data().unwrap()[1..]).rlp.val_at(12)
+            }
         }
     }
 }
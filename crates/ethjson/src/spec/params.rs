@@ -18,6 +18,7 @@
 
 use crate::hash::{Address, H256};
 use crate::uint::{self, Uint};
+use std::collections::BTreeMap;
 
 /// Spec params.
 #[derive(Debug, PartialEq, Deserialize)]
@@ -95,8 +96,6 @@ pub struct Params {
     /// See `CommonParams` docs.
     pub eip2028_transition: Option<Uint>,
     /// See `CommonParams` docs.
-    pub eip2315_transition: Option<Uint>,
-    /// See `CommonParams` docs.
     pub eip2929_transition: Option<Uint>,
     /// See `CommonParams` docs.
     pub eip2930_transition: Option<Uint>,
@@ -111,6 +110,58 @@ pub struct Params {
     /// See `CommonParams` docs.
     pub eip3607_transition: Option<Uint>,
     /// See `CommonParams` docs.
+    pub eip3651_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip3855_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip3860_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip1153_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip5656_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip6780_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip7702_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip2935_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub eip2935_contract_address: Option<Address>,
+    /// See `CommonParams` docs.
+    pub l1_attributes_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub l1_attributes_contract_address: Option<Address>,
+    /// See `CommonParams` docs.
+    pub l1_fee_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub l1_fee_oracle: Option<Address>,
+    /// See `CommonParams` docs.
+    pub l1_fee_vault: Option<Address>,
+    /// See `CommonParams` docs.
+    pub tx_data_gas_factor: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub tx_base_size: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub tx_data_compression_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub fee_distribution_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub priority_fee_split: Option<BTreeMap<Address, Uint>>,
+    /// See `CommonParams` docs.
+    pub base_fee_split: Option<BTreeMap<Address, Uint>>,
+    /// See `CommonParams` docs.
+    pub l1_fee_split: Option<BTreeMap<Address, Uint>>,
+    /// See `CommonParams` docs.
+    pub enqueued_tx_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub enqueued_gas_limit: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub deployer_allowlist_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub deployer_allowlist_disable_transition: Option<Uint>,
+    /// See `CommonParams` docs.
+    pub deployer_allowlist_contract_address: Option<Address>,
+    /// See `CommonParams` docs.
     pub dust_protection_transition: Option<Uint>,
     /// See `CommonParams` docs.
     pub nonce_cap_increment: Option<Uint>,
@@ -40,6 +40,9 @@ pub enum ForkSpec {
     Berlin,
     London,
     BerlinToLondonAt5,
+    Shanghai,
+    Cancun,
+    Prague,
 }
 
 /// Spec deserialization.
@@ -0,0 +1,319 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-2537 operations on BLS12-381, backed by `blst`, enabled by the `bls12-381` feature.
+
+use blst::{
+    blst_bendian_from_fp, blst_final_exp, blst_fp, blst_fp12, blst_fp12_is_one, blst_fp12_mul, blst_fp2, blst_fp_from_bendian, blst_map_to_g1, blst_map_to_g2, blst_miller_loop, blst_p1, blst_p1_add_or_double, blst_p1_add_or_double_affine, blst_p1_affine, blst_p1_affine_in_g1, blst_p1_affine_is_inf, blst_p1_affine_on_curve, blst_p1_from_affine, blst_p1_mult, blst_p1_to_affine, blst_p2, blst_p2_add_or_double, blst_p2_add_or_double_affine, blst_p2_affine, blst_p2_affine_in_g2, blst_p2_affine_is_inf, blst_p2_affine_on_curve, blst_p2_from_affine, blst_p2_mult, blst_p2_to_affine, blst_scalar, blst_scalar_from_bendian
+};
+use core::ptr;
+
+/// Length of a big endian field element without padding.
+const FP_BYTE_LENGTH: usize = 48;
+/// Length of a field element as encoded by EIP-2537, left padded with zeroes.
+pub const SERIALIZED_FP_BYTE_LENGTH: usize = 64;
+/// Length of an encoded element of the quadratic extension field.
+pub const SERIALIZED_FP2_BYTE_LENGTH: usize = 2 * SERIALIZED_FP_BYTE_LENGTH;
+/// Length of an encoded G1 point.
+pub const SERIALIZED_G1_POINT_BYTE_LENGTH: usize = 2 * SERIALIZED_FP_BYTE_LENGTH;
+/// Length of an encoded G2 point.
+pub const SERIALIZED_G2_POINT_BYTE_LENGTH: usize = 2 * SERIALIZED_FP2_BYTE_LENGTH;
+/// Length of an encoded scalar.
+pub const SCALAR_BYTE_LENGTH: usize = 32;
+
+/// Base field modulus, big endian.
+const MODULUS: [u8; FP_BYTE_LENGTH] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7,
+    0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24,
+    0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xab,
+];
+
+type Result<T> = core::result::Result<T, &'static str>;
+
+fn decode_fp(input: &[u8]) -> Result<blst_fp> {
+    let (padding, be) = input.split_at(SERIALIZED_FP_BYTE_LENGTH - FP_BYTE_LENGTH);
+    if padding.iter().any(|b| *b != 0) {
+        return Err("Invalid field element padding");
+    }
+    // both slices are big endian and of equal length, so they compare as numbers
+    if be >= &MODULUS[..] {
+        return Err("Field element is not canonical");
+    }
+    let mut fp = blst_fp::default();
+    // SAFETY: `be` is exactly `FP_BYTE_LENGTH` bytes long.
+    unsafe { blst_fp_from_bendian(&mut fp, be.as_ptr()) };
+    Ok(fp)
+}
+
+fn encode_fp(fp: &blst_fp, output: &mut [u8]) {
+    let offset = SERIALIZED_FP_BYTE_LENGTH - FP_BYTE_LENGTH;
+    // SAFETY: the output slice holds `FP_BYTE_LENGTH` bytes past the padding.
+    unsafe { blst_bendian_from_fp(output[offset..SERIALIZED_FP_BYTE_LENGTH].as_mut_ptr(), fp) };
+}
+
+fn decode_fp2(input: &[u8]) -> Result<blst_fp2> {
+    let c0 = decode_fp(&input[..SERIALIZED_FP_BYTE_LENGTH])?;
+    let c1 = decode_fp(&input[SERIALIZED_FP_BYTE_LENGTH..SERIALIZED_FP2_BYTE_LENGTH])?;
+    Ok(blst_fp2 { fp: [c0, c1] })
+}
+
+fn encode_fp2(fp2: &blst_fp2, output: &mut [u8]) {
+    encode_fp(&fp2.fp[0], &mut output[..SERIALIZED_FP_BYTE_LENGTH]);
+    encode_fp(&fp2.fp[1], &mut output[SERIALIZED_FP_BYTE_LENGTH..SERIALIZED_FP2_BYTE_LENGTH]);
+}
+
+/// Decodes a G1 point. The encoding of all zeroes is the point at infinity. Subgroup membership
+/// is only enforced for pairings.
+fn decode_g1(input: &[u8], subgroup_check: bool) -> Result<blst_p1_affine> {
+    let point = blst_p1_affine {
+        x: decode_fp(&input[..SERIALIZED_FP_BYTE_LENGTH])?,
+        y: decode_fp(&input[SERIALIZED_FP_BYTE_LENGTH..SERIALIZED_G1_POINT_BYTE_LENGTH])?,
+    };
+    // SAFETY: `point` is a valid `blst` value.
+    if unsafe { !blst_p1_affine_on_curve(&point) } {
+        return Err("G1 point is not on curve");
+    }
+    if subgroup_check && unsafe { !blst_p1_affine_in_g1(&point) } {
+        return Err("G1 point is not in the correct subgroup");
+    }
+    Ok(point)
+}
+
+fn encode_g1(point: &blst_p1) -> [u8; SERIALIZED_G1_POINT_BYTE_LENGTH] {
+    let mut affine = blst_p1_affine::default();
+    // SAFETY: both arguments are valid `blst` values.
+    unsafe { blst_p1_to_affine(&mut affine, point) };
+    let mut output = [0u8; SERIALIZED_G1_POINT_BYTE_LENGTH];
+    encode_fp(&affine.x, &mut output[..SERIALIZED_FP_BYTE_LENGTH]);
+    encode_fp(&affine.y, &mut output[SERIALIZED_FP_BYTE_LENGTH..]);
+    output
+}
+
+/// Decodes a G2 point, see `decode_g1`.
+fn decode_g2(input: &[u8], subgroup_check: bool) -> Result<blst_p2_affine> {
+    let point = blst_p2_affine {
+        x: decode_fp2(&input[..SERIALIZED_FP2_BYTE_LENGTH])?,
+        y: decode_fp2(&input[SERIALIZED_FP2_BYTE_LENGTH..SERIALIZED_G2_POINT_BYTE_LENGTH])?,
+    };
+    // SAFETY: `point` is a valid `blst` value.
+    if unsafe { !blst_p2_affine_on_curve(&point) } {
+        return Err("G2 point is not on curve");
+    }
+    if subgroup_check && unsafe { !blst_p2_affine_in_g2(&point) } {
+        return Err("G2 point is not in the correct subgroup");
+    }
+    Ok(point)
+}
+
+fn encode_g2(point: &blst_p2) -> [u8; SERIALIZED_G2_POINT_BYTE_LENGTH] {
+    let mut affine = blst_p2_affine::default();
+    // SAFETY: both arguments are valid `blst` values.
+    unsafe { blst_p2_to_affine(&mut affine, point) };
+    let mut output = [0u8; SERIALIZED_G2_POINT_BYTE_LENGTH];
+    encode_fp2(&affine.x, &mut output[..SERIALIZED_FP2_BYTE_LENGTH]);
+    encode_fp2(&affine.y, &mut output[SERIALIZED_FP2_BYTE_LENGTH..]);
+    output
+}
+
+/// Multiplies by the full 256 bit scalar, without reducing it by the group order.
+fn g1_mul_raw(point: &blst_p1_affine, scalar: &[u8]) -> blst_p1 {
+    let mut s = blst_scalar::default();
+    let mut p = blst_p1::default();
+    let mut result = blst_p1::default();
+    // SAFETY: `scalar` is exactly `SCALAR_BYTE_LENGTH` bytes long, the rest are `blst` values.
+    unsafe {
+        blst_scalar_from_bendian(&mut s, scalar.as_ptr());
+        blst_p1_from_affine(&mut p, point);
+        blst_p1_mult(&mut result, &p, s.b.as_ptr(), SCALAR_BYTE_LENGTH * 8);
+    }
+    result
+}
+
+/// Multiplies by the full 256 bit scalar, without reducing it by the group order.
+fn g2_mul_raw(point: &blst_p2_affine, scalar: &[u8]) -> blst_p2 {
+    let mut s = blst_scalar::default();
+    let mut p = blst_p2::default();
+    let mut result = blst_p2::default();
+    // SAFETY: `scalar` is exactly `SCALAR_BYTE_LENGTH` bytes long, the rest are `blst` values.
+    unsafe {
+        blst_scalar_from_bendian(&mut s, scalar.as_ptr());
+        blst_p2_from_affine(&mut p, point);
+        blst_p2_mult(&mut result, &p, s.b.as_ptr(), SCALAR_BYTE_LENGTH * 8);
+    }
+    result
+}
+
+/// Adds two G1 points.
+pub fn g1_add(input: &[u8]) -> Result<[u8; SERIALIZED_G1_POINT_BYTE_LENGTH]> {
+    if input.len() != 2 * SERIALIZED_G1_POINT_BYTE_LENGTH {
+        return Err("Invalid input length for G1 addition");
+    }
+    let a = decode_g1(&input[..SERIALIZED_G1_POINT_BYTE_LENGTH], false)?;
+    let b = decode_g1(&input[SERIALIZED_G1_POINT_BYTE_LENGTH..], false)?;
+
+    let mut p = blst_p1::default();
+    let mut result = blst_p1::default();
+    // SAFETY: all arguments are valid `blst` values.
+    unsafe {
+        blst_p1_from_affine(&mut p, &a);
+        blst_p1_add_or_double_affine(&mut result, &p, &b);
+    }
+    Ok(encode_g1(&result))
+}
+
+/// Multiplies a G1 point by a scalar.
+pub fn g1_mul(input: &[u8]) -> Result<[u8; SERIALIZED_G1_POINT_BYTE_LENGTH]> {
+    if input.len() != SERIALIZED_G1_POINT_BYTE_LENGTH + SCALAR_BYTE_LENGTH {
+        return Err("Invalid input length for G1 multiplication");
+    }
+    let point = decode_g1(&input[..SERIALIZED_G1_POINT_BYTE_LENGTH], false)?;
+    Ok(encode_g1(&g1_mul_raw(&point, &input[SERIALIZED_G1_POINT_BYTE_LENGTH..])))
+}
+
+/// Sums the products of G1 points and scalars.
+pub fn g1_multiexp(input: &[u8]) -> Result<[u8; SERIALIZED_G1_POINT_BYTE_LENGTH]> {
+    const PAIR_LENGTH: usize = SERIALIZED_G1_POINT_BYTE_LENGTH + SCALAR_BYTE_LENGTH;
+    if input.is_empty() || input.len() % PAIR_LENGTH != 0 {
+        return Err("Invalid input length for G1 multiexponentiation");
+    }
+
+    let mut acc = blst_p1::default();
+    for pair in input.chunks_exact(PAIR_LENGTH) {
+        let point = decode_g1(&pair[..SERIALIZED_G1_POINT_BYTE_LENGTH], false)?;
+        let product = g1_mul_raw(&point, &pair[SERIALIZED_G1_POINT_BYTE_LENGTH..]);
+        let prev = acc;
+        // SAFETY: all arguments are valid `blst` values.
+        unsafe { blst_p1_add_or_double(&mut acc, &prev, &product) };
+    }
+    Ok(encode_g1(&acc))
+}
+
+/// Adds two G2 points.
+pub fn g2_add(input: &[u8]) -> Result<[u8; SERIALIZED_G2_POINT_BYTE_LENGTH]> {
+    if input.len() != 2 * SERIALIZED_G2_POINT_BYTE_LENGTH {
+        return Err("Invalid input length for G2 addition");
+    }
+    let a = decode_g2(&input[..SERIALIZED_G2_POINT_BYTE_LENGTH], false)?;
+    let b = decode_g2(&input[SERIALIZED_G2_POINT_BYTE_LENGTH..], false)?;
+
+    let mut p = blst_p2::default();
+    let mut result = blst_p2::default();
+    // SAFETY: all arguments are valid `blst` values.
+    unsafe {
+        blst_p2_from_affine(&mut p, &a);
+        blst_p2_add_or_double_affine(&mut result, &p, &b);
+    }
+    Ok(encode_g2(&result))
+}
+
+/// Multiplies a G2 point by a scalar.
+pub fn g2_mul(input: &[u8]) -> Result<[u8; SERIALIZED_G2_POINT_BYTE_LENGTH]> {
+    if input.len() != SERIALIZED_G2_POINT_BYTE_LENGTH + SCALAR_BYTE_LENGTH {
+        return Err("Invalid input length for G2 multiplication");
+    }
+    let point = decode_g2(&input[..SERIALIZED_G2_POINT_BYTE_LENGTH], false)?;
+    Ok(encode_g2(&g2_mul_raw(&point, &input[SERIALIZED_G2_POINT_BYTE_LENGTH..])))
+}
+
+/// Sums the products of G2 points and scalars.
+pub fn g2_multiexp(input: &[u8]) -> Result<[u8; SERIALIZED_G2_POINT_BYTE_LENGTH]> {
+    const PAIR_LENGTH: usize = SERIALIZED_G2_POINT_BYTE_LENGTH + SCALAR_BYTE_LENGTH;
+    if input.is_empty() || input.len() % PAIR_LENGTH != 0 {
+        return Err("Invalid input length for G2 multiexponentiation");
+    }
+
+    let mut acc = blst_p2::default();
+    for pair in input.chunks_exact(PAIR_LENGTH) {
+        let point = decode_g2(&pair[..SERIALIZED_G2_POINT_BYTE_LENGTH], false)?;
+        let product = g2_mul_raw(&point, &pair[SERIALIZED_G2_POINT_BYTE_LENGTH..]);
+        let prev = acc;
+        // SAFETY: all arguments are valid `blst` values.
+        unsafe { blst_p2_add_or_double(&mut acc, &prev, &product) };
+    }
+    Ok(encode_g2(&acc))
+}
+
+/// Checks whether the product of the pairings of all (G1, G2) pairs is the identity.
+pub fn pairing(input: &[u8]) -> Result<[u8; SCALAR_BYTE_LENGTH]> {
+    const PAIR_LENGTH: usize = SERIALIZED_G1_POINT_BYTE_LENGTH + SERIALIZED_G2_POINT_BYTE_LENGTH;
+    if input.is_empty() || input.len() % PAIR_LENGTH != 0 {
+        return Err("Invalid input length for pairing");
+    }
+
+    let mut acc: Option<blst_fp12> = None;
+    for pair in input.chunks_exact(PAIR_LENGTH) {
+        let p = decode_g1(&pair[..SERIALIZED_G1_POINT_BYTE_LENGTH], true)?;
+        let q = decode_g2(&pair[SERIALIZED_G1_POINT_BYTE_LENGTH..], true)?;
+        // SAFETY: both points are valid `blst` values.
+        if unsafe { blst_p1_affine_is_inf(&p) || blst_p2_affine_is_inf(&q) } {
+            // pairing with infinity is the identity and does not change the product
+            continue;
+        }
+
+        let mut ml = blst_fp12::default();
+        // SAFETY: all arguments are valid `blst` values.
+        unsafe { blst_miller_loop(&mut ml, &q, &p) };
+        acc = Some(match acc {
+            Some(prev) => {
+                let mut product = blst_fp12::default();
+                // SAFETY: all arguments are valid `blst` values.
+                unsafe { blst_fp12_mul(&mut product, &prev, &ml) };
+                product
+            }
+            None => ml,
+        });
+    }
+
+    let is_one = match acc {
+        Some(ml) => {
+            let mut result = blst_fp12::default();
+            // SAFETY: all arguments are valid `blst` values.
+            unsafe {
+                blst_final_exp(&mut result, &ml);
+                blst_fp12_is_one(&result)
+            }
+        }
+        None => true,
+    };
+
+    let mut output = [0u8; SCALAR_BYTE_LENGTH];
+    output[SCALAR_BYTE_LENGTH - 1] = is_one as u8;
+    Ok(output)
+}
+
+/// Maps a base field element to G1.
+pub fn map_fp_to_g1(input: &[u8]) -> Result<[u8; SERIALIZED_G1_POINT_BYTE_LENGTH]> {
+    if input.len() != SERIALIZED_FP_BYTE_LENGTH {
+        return Err("Invalid input length for Fp to G1 mapping");
+    }
+    let fp = decode_fp(input)?;
+    let mut point = blst_p1::default();
+    // SAFETY: `fp` is a valid `blst` value, a null second element selects the plain map.
+    unsafe { blst_map_to_g1(&mut point, &fp, ptr::null()) };
+    Ok(encode_g1(&point))
+}
+
+/// Maps an element of the quadratic extension field to G2.
+pub fn map_fp2_to_g2(input: &[u8]) -> Result<[u8; SERIALIZED_G2_POINT_BYTE_LENGTH]> {
+    if input.len() != SERIALIZED_FP2_BYTE_LENGTH {
+        return Err("Invalid input length for Fp2 to G2 mapping");
+    }
+    let fp2 = decode_fp2(input)?;
+    let mut point = blst_p2::default();
+    // SAFETY: `fp2` is a valid `blst` value, a null second element selects the plain map.
+    unsafe { blst_map_to_g2(&mut point, &fp2, ptr::null()) };
+    Ok(encode_g2(&point))
+}
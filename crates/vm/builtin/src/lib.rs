@@ -33,18 +33,21 @@ use core::mem::size_of;
 use core::{convert::TryFrom, str::FromStr};
 
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
-use crypto::hash::{ripemd160, sha256};
+use crypto::hash::{keccak, ripemd160, sha256};
 use crypto::publickey::{recover, Signature};
 use eip_152::compress;
-use ethereum_types::{H256, U256};
+use ethereum_types::{Address, H256, U256};
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
+#[cfg(feature = "p256-verify")]
+use p256::ecdsa::{signature::hazmat::PrehashVerifier, Signature as P256Signature, VerifyingKey};
 use parity_bytes::BytesRef;
 
-#[cfg(feature = "std")]
-use eth_pairings::public_interface::eip2537::{
-    EIP2537Executor, SCALAR_BYTE_LENGTH, SERIALIZED_G1_POINT_BYTE_LENGTH, SERIALIZED_G2_POINT_BYTE_LENGTH
-};
+#[cfg(feature = "bls12-381")]
+mod bls12;
+
+#[cfg(feature = "bls12-381")]
+use bls12::{SCALAR_BYTE_LENGTH, SERIALIZED_G1_POINT_BYTE_LENGTH, SERIALIZED_G2_POINT_BYTE_LENGTH};
 
 /// Native implementation of a built-in contract.
 pub trait Implementation: Send + Sync {
@@ -86,13 +89,13 @@ pub enum Pricing {
     Linear(Linear),
     Modexp(ModexpPricer),
     Modexp2565(Modexp2565Pricer),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     Bls12Pairing(Bls12PairingPricer),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     Bls12ConstOperations(Bls12ConstOperations),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     Bls12MultiexpG1(Bls12MultiexpPricerG1),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     Bls12MultiexpG2(Bls12MultiexpPricerG2),
 }
 
@@ -105,13 +108,13 @@ impl Pricer for Pricing {
             Pricing::Linear(inner) => inner.cost(input),
             Pricing::Modexp(inner) => inner.cost(input),
             Pricing::Modexp2565(inner) => inner.cost(input),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             Pricing::Bls12Pairing(inner) => inner.cost(input),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             Pricing::Bls12ConstOperations(inner) => inner.cost(input),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             Pricing::Bls12MultiexpG1(inner) => inner.cost(input),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             Pricing::Bls12MultiexpG2(inner) => inner.cost(input),
         }
     }
@@ -329,19 +332,19 @@ impl Pricer for Modexp2565Pricer {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 /// Bls12 pairing price
 #[derive(Debug, Copy, Clone)]
-struct Bls12PairingPrice {
-    base: u64,
-    pair: u64,
+pub struct Bls12PairingPrice {
+    pub base: u64,
+    pub pair: u64,
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 /// bls12_pairing pricing model. This computes a price using a base cost and a cost per pair.
 #[derive(Debug)]
 pub struct Bls12PairingPricer {
-    price: Bls12PairingPrice,
+    pub price: Bls12PairingPrice,
 }
 
 /// Pricing for constant Bls12 operations (ADD and MUL in G1 and G2, as well as mappings)
@@ -490,8 +493,8 @@ pub const BLS12_MULTIEXP_MAX_DISCOUNT: u64 = 174;
 pub const BLS12_MULTIEXP_PAIRS_FOR_MAX_DISCOUNT: usize = 128;
 /// Divisor for discounts table
 pub const BLS12_MULTIEXP_DISCOUNT_DIVISOR: u64 = 1000;
+#[cfg(feature = "bls12-381")]
 /// Length of single G1 + G2 points pair for pairing operation
-#[cfg(feature = "std")]
 pub const BLS12_G1_AND_G2_PAIR_LEN: usize =
     SERIALIZED_G1_POINT_BYTE_LENGTH + SERIALIZED_G2_POINT_BYTE_LENGTH;
 
@@ -504,7 +507,7 @@ pub trait PointScalarLength: Copy + Clone + fmt::Debug + Send + Sync {
 #[derive(Clone, Copy, Debug)]
 pub struct G1Marker;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl PointScalarLength for G1Marker {
     const LENGTH: usize = SERIALIZED_G1_POINT_BYTE_LENGTH + SCALAR_BYTE_LENGTH;
 }
@@ -512,29 +515,40 @@ impl PointScalarLength for G1Marker {
 #[derive(Clone, Copy, Debug)]
 pub struct G2Marker;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl PointScalarLength for G2Marker {
     const LENGTH: usize = SERIALIZED_G2_POINT_BYTE_LENGTH + SCALAR_BYTE_LENGTH;
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 /// Pricing for constant Bls12 operations (ADD and MUL in G1 and G2, as well as mappings)
 #[derive(Debug, Copy, Clone)]
 pub struct Bls12MultiexpPricer<P: PointScalarLength> {
     /// Base const of the operation (G1 or G2 multiplication)
     pub base_price: Bls12ConstOperations,
 
-    _marker: std::marker::PhantomData<P>,
+    _marker: core::marker::PhantomData<P>,
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
+impl<P: PointScalarLength> Bls12MultiexpPricer<P> {
+    /// Create a multiexp pricer with the given base price of a single multiplication.
+    pub fn new(base_price: u64) -> Self {
+        Bls12MultiexpPricer {
+            base_price: Bls12ConstOperations { price: base_price },
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "bls12-381")]
 impl Pricer for Bls12ConstOperations {
     fn cost(&self, _input: &[u8]) -> U256 {
         self.price.into()
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Pricer for Bls12PairingPricer {
     fn cost(&self, input: &[u8]) -> U256 {
         U256::from(self.price.base)
@@ -542,7 +556,7 @@ impl Pricer for Bls12PairingPricer {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl<P: PointScalarLength> Pricer for Bls12MultiexpPricer<P> {
     fn cost(&self, input: &[u8]) -> U256 {
         let num_pairs = input.len() / P::LENGTH;
@@ -560,11 +574,11 @@ impl<P: PointScalarLength> Pricer for Bls12MultiexpPricer<P> {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 /// Multiexp pricer in G1
 pub type Bls12MultiexpPricerG1 = Bls12MultiexpPricer<G1Marker>;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 /// Multiexp pricer in G2
 pub type Bls12MultiexpPricerG2 = Bls12MultiexpPricer<G2Marker>;
 
@@ -657,16 +671,10 @@ impl From<ethjson::spec::builtin::Pricing> for Pricing {
                 })
             }
             ethjson::spec::builtin::Pricing::Bls12G1Multiexp(pricer) => {
-                Pricing::Bls12MultiexpG1(Bls12MultiexpPricerG1 {
-                    base_price: Bls12ConstOperations { price: pricer.base },
-                    _marker: std::marker::PhantomData,
-                })
+                Pricing::Bls12MultiexpG1(Bls12MultiexpPricerG1::new(pricer.base))
             }
             ethjson::spec::builtin::Pricing::Bls12G2Multiexp(pricer) => {
-                Pricing::Bls12MultiexpG2(Bls12MultiexpPricerG2 {
-                    base_price: Bls12ConstOperations { price: pricer.base },
-                    _marker: std::marker::PhantomData,
-                })
+                Pricing::Bls12MultiexpG2(Bls12MultiexpPricerG2::new(pricer.base))
             }
         }
     }
@@ -692,33 +700,38 @@ pub enum EthereumBuiltin {
     Bn128Pairing(Bn128Pairing),
     /// blake2_f (The Blake2 compression function F, EIP-152)
     Blake2F(Blake2F),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 addition in g1
     Bls12G1Add(Bls12G1Add),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 multiplication in g1
     Bls12G1Mul(Bls12G1Mul),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 multiexponentiation in g1
     Bls12G1MultiExp(Bls12G1MultiExp),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 addition in g2
     Bls12G2Add(Bls12G2Add),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 multiplication in g2
     Bls12G2Mul(Bls12G2Mul),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 multiexponentiation in g2
     Bls12G2MultiExp(Bls12G2MultiExp),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 pairing
     Bls12Pairing(Bls12Pairing),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 fp to g1 mapping
     Bls12MapFpToG1(Bls12MapFpToG1),
-    #[cfg(feature = "std")]
+    #[cfg(feature = "bls12-381")]
     /// bls12_381 fp2 to g2 mapping
     Bls12MapFp2ToG2(Bls12MapFp2ToG2),
+    #[cfg(feature = "p256-verify")]
+    /// secp256r1 signature verification (RIP-7212)
+    P256Verify(P256Verify),
+    /// L2 to L1 message passing
+    MessagePasser(MessagePasser),
 }
 
 #[cfg(feature = "std")]
@@ -745,6 +758,8 @@ impl FromStr for EthereumBuiltin {
             "bls12_381_pairing" => Ok(EthereumBuiltin::Bls12Pairing(Bls12Pairing)),
             "bls12_381_fp_to_g1" => Ok(EthereumBuiltin::Bls12MapFpToG1(Bls12MapFpToG1)),
             "bls12_381_fp2_to_g2" => Ok(EthereumBuiltin::Bls12MapFp2ToG2(Bls12MapFp2ToG2)),
+            "p256_verify" => Ok(EthereumBuiltin::P256Verify(P256Verify)),
+            "message_passer" => Ok(EthereumBuiltin::MessagePasser(MessagePasser)),
             _ => return Err(format!("invalid builtin name: {}", name)),
         }
     }
@@ -762,24 +777,27 @@ impl Implementation for EthereumBuiltin {
             EthereumBuiltin::Bn128Mul(inner) => inner.execute(input, output),
             EthereumBuiltin::Bn128Pairing(inner) => inner.execute(input, output),
             EthereumBuiltin::Blake2F(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12G1Add(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12G1Mul(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12G1MultiExp(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12G2Add(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12G2Mul(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12G2MultiExp(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12Pairing(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12MapFpToG1(inner) => inner.execute(input, output),
-            #[cfg(feature = "std")]
+            #[cfg(feature = "bls12-381")]
             EthereumBuiltin::Bls12MapFp2ToG2(inner) => inner.execute(input, output),
+            #[cfg(feature = "p256-verify")]
+            EthereumBuiltin::P256Verify(inner) => inner.execute(input, output),
+            EthereumBuiltin::MessagePasser(inner) => inner.execute(input, output),
         }
     }
 }
@@ -847,6 +865,33 @@ pub struct Bls12MapFpToG1;
 /// The Bls12MapFp2ToG2 builtin.
 pub struct Bls12MapFp2ToG2;
 
+#[derive(Debug)]
+/// The P256Verify builtin.
+pub struct P256Verify;
+
+#[derive(Debug)]
+/// The L2 to L1 message passer builtin.
+///
+/// Appends messages to an accumulator kept in the storage of the builtin account and emits the
+/// `MessageSent(uint64,address,address,bytes32,bytes)` event. It needs state access, so the
+/// executive runs it through `MessagePasser::pass` instead of `execute`.
+pub struct MessagePasser;
+
+/// A message appended to the accumulator of the message passer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassedMessage {
+    /// Index of the message, messages are numbered from zero.
+    pub index: u64,
+    /// L1 recipient of the message.
+    pub target: Address,
+    /// L2 account sending the message.
+    pub sender: Address,
+    /// Accumulator root including this message.
+    pub root: H256,
+    /// Message payload.
+    pub message: Vec<u8>,
+}
+
 impl Implementation for Identity {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
         output.write(0, input);
@@ -1051,147 +1096,194 @@ impl Implementation for Modexp {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12G1Add {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::g1_add(input);
-
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
-
-                Ok(())
-            }
-            Err(_e) => Err("Bls12G1Add error"),
-        }
+        let result = bls12::g1_add(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12G1Mul {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::g1_mul(input);
-
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
-
-                Ok(())
-            }
-            Err(_e) => Err("Bls12G1Mul error"),
-        }
+        let result = bls12::g1_mul(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12G1MultiExp {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::g1_multiexp(input);
-
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
-
-                Ok(())
-            }
-            Err(_e) => Err("Bls12G1MultiExp error"),
-        }
+        let result = bls12::g1_multiexp(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12G2Add {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::g2_add(input);
-
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
-
-                Ok(())
-            }
-            Err(_e) => Err("Bls12G2Add error"),
-        }
+        let result = bls12::g2_add(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12G2Mul {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::g2_mul(input);
-
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
-
-                Ok(())
-            }
-            Err(_e) => Err("Bls12G2Mul error"),
-        }
+        let result = bls12::g2_mul(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12G2MultiExp {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::g2_multiexp(input);
-
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
-
-                Ok(())
-            }
-            Err(_e) => Err("Bls12G2MultiExp error"),
-        }
+        let result = bls12::g2_multiexp(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "bls12-381")]
 impl Implementation for Bls12Pairing {
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::pair(input);
+        let result = bls12::pairing(input)?;
+        output.write(0, &result[..]);
+        Ok(())
+    }
+}
 
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12MapFpToG1 {
+    fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+        let result = bls12::map_fp_to_g1(input)?;
+        output.write(0, &result[..]);
+        Ok(())
+    }
+}
 
-                Ok(())
-            }
-            Err(_e) => Err("Bls12Pairing error"),
-        }
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12MapFp2ToG2 {
+    fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+        let result = bls12::map_fp2_to_g2(input)?;
+        output.write(0, &result[..]);
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
-impl Implementation for Bls12MapFpToG1 {
+#[cfg(feature = "p256-verify")]
+impl Implementation for P256Verify {
+    /// Format of `input`:
+    /// [32 bytes for message hash][32 bytes for r][32 bytes for s][32 bytes for x][32 bytes for y]
+    ///
+    /// A valid signature returns 1 as a 32 byte word. Any invalid input, including a wrong length,
+    /// returns no data rather than failing the call.
     fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::map_fp_to_g1(input);
+        const P256_VERIFY_ARG_LEN: usize = 160;
+        if input.len() != P256_VERIFY_ARG_LEN {
+            return Ok(());
+        }
 
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
+        let (hash, rest) = input.split_at(32);
+        let (signature, public) = rest.split_at(64);
+        let signature = match P256Signature::from_slice(signature) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(()),
+        };
+        // uncompressed SEC1 encoding of the public key
+        let mut sec1 = [0u8; 65];
+        sec1[0] = 0x04;
+        sec1[1..].copy_from_slice(public);
+        let key = match VerifyingKey::from_sec1_bytes(&sec1) {
+            Ok(key) => key,
+            Err(_) => return Ok(()),
+        };
 
-                Ok(())
-            }
-            Err(_e) => Err("Bls12MapFpToG1 error"),
+        if key.verify_prehash(hash, &signature).is_ok() {
+            output.write(0, H256::from_low_u64_be(1).as_bytes());
         }
+        Ok(())
     }
 }
 
-#[cfg(feature = "std")]
-impl Implementation for Bls12MapFp2ToG2 {
-    fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
-        let result = EIP2537Executor::map_fp2_to_g2(input);
+impl Implementation for MessagePasser {
+    fn execute(&self, _input: &[u8], _output: &mut BytesRef) -> Result<(), &'static str> {
+        Err("Message passer requires state access")
+    }
+}
 
-        match result {
-            Ok(result_bytes) => {
-                output.write(0, &result_bytes[..]);
+impl MessagePasser {
+    /// Topic of the `MessageSent` event.
+    pub fn event_id() -> H256 {
+        keccak("MessageSent(uint64,address,address,bytes32,bytes)".as_bytes())
+    }
 
-                Ok(())
-            }
-            Err(_e) => Err("Bls12MapFp2ToG2 error"),
+    /// Storage key holding the index of the next message.
+    pub fn index_key() -> H256 {
+        keccak("message_passer.index".as_bytes())
+    }
+
+    /// Storage key holding the accumulator root.
+    pub fn root_key() -> H256 {
+        keccak("message_passer.root".as_bytes())
+    }
+
+    /// Append the message in `input` sent by `sender` to the accumulator at `next_index` and
+    /// `root`.
+    ///
+    /// Format of `input`: [20 bytes for target][message]
+    ///
+    /// The new root is `keccak(root ++ keccak(index ++ target ++ sender ++ message))` with the
+    /// index encoded as 8 big endian bytes.
+    pub fn pass(
+        &self, input: &[u8], sender: Address, next_index: u64, root: H256,
+    ) -> Result<PassedMessage, &'static str> {
+        if input.len() < 20 {
+            return Err("Message target missing");
         }
+        let index = next_index;
+        index.checked_add(1).ok_or("Message index overflow")?;
+        let target = Address::from_slice(&input[..20]);
+        let message = input[20..].to_vec();
+
+        let mut preimage = Vec::with_capacity(48 + message.len());
+        preimage.extend_from_slice(&index.to_be_bytes());
+        preimage.extend_from_slice(target.as_bytes());
+        preimage.extend_from_slice(sender.as_bytes());
+        preimage.extend_from_slice(&message);
+        let message_hash = keccak(&preimage);
+        let root = keccak([root.as_bytes(), message_hash.as_bytes()].concat());
+
+        Ok(PassedMessage { index, target, sender, root, message })
+    }
+}
+
+impl PassedMessage {
+    /// Topics of the `MessageSent` event, the index, target and sender are indexed.
+    pub fn topics(&self) -> Vec<H256> {
+        vec![
+            MessagePasser::event_id(),
+            H256::from_low_u64_be(self.index),
+            H256::from(self.target),
+            H256::from(self.sender),
+        ]
+    }
+
+    /// ABI encoded data of the `MessageSent` event, the accumulator root and the message.
+    pub fn data(&self) -> Vec<u8> {
+        let padded_len = (self.message.len() + 31) / 32 * 32;
+        let mut data = vec![0u8; 96 + padded_len];
+        data[..32].copy_from_slice(self.root.as_bytes());
+        U256::from(64).to_big_endian(&mut data[32..64]);
+        U256::from(self.message.len()).to_big_endian(&mut data[64..96]);
+        data[96..96 + self.message.len()].copy_from_slice(&self.message);
+        data
     }
 }
 
@@ -1359,7 +1451,6 @@ mod tests {
     use ethjson::spec::builtin::{
         AltBn128Pairing as JsonAltBn128PairingPricing, Builtin as JsonBuiltin, Linear as JsonLinearPricing, Pricing as JsonPricing, PricingAt
     };
-    use hex::FromHex;
     use hex_literal::hex;
     use macros::map;
     use maplit::btreemap;
@@ -2624,6 +2715,147 @@ mod tests {
         assert_eq!(&output[..], &expected[..]);
     }
 
+    #[test]
+    fn bls12_381_g1_add_rejects_invalid_encoding() {
+        let f = Builtin {
+            pricer: btreemap![0 => Pricing::Bls12ConstOperations(Bls12ConstOperations{price: 1})],
+            native: EthereumBuiltin::from_str("bls12_381_g1_add").unwrap(),
+        };
+        let mut output = [0u8; 128];
+
+        let mut input = [0u8; 256];
+        assert_eq!(
+            f.execute(&input[..255], &mut BytesRef::Fixed(&mut output[..])),
+            Err("Invalid input length for G1 addition")
+        );
+
+        input[0] = 1;
+        assert_eq!(
+            f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])),
+            Err("Invalid field element padding")
+        );
+
+        // the field modulus itself is not a canonical field element
+        input[0] = 0;
+        input[16..64].copy_from_slice(&hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"));
+        assert_eq!(
+            f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])),
+            Err("Field element is not canonical")
+        );
+
+        // (1, 0) is not on the curve
+        input[16..64].copy_from_slice(&[0u8; 48]);
+        input[63] = 1;
+        assert_eq!(
+            f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])),
+            Err("G1 point is not on curve")
+        );
+    }
+
+    #[test]
+    fn bls12_381_infinity() {
+        let g1_add = Builtin {
+            pricer: btreemap![0 => Pricing::Bls12ConstOperations(Bls12ConstOperations{price: 1})],
+            native: EthereumBuiltin::from_str("bls12_381_g1_add").unwrap(),
+        };
+        let pairing = Builtin {
+            pricer: btreemap![0 => 	Pricing::Bls12Pairing(Bls12PairingPricer{price: Bls12PairingPrice{base: 1, pair: 1}})],
+            native: EthereumBuiltin::from_str("bls12_381_pairing").unwrap(),
+        };
+
+        let mut output = [0xffu8; 128];
+        g1_add
+            .execute(&[0u8; 256][..], &mut BytesRef::Fixed(&mut output[..]))
+            .expect("Builtin should not fail");
+        assert_eq!(&output[..], &[0u8; 128][..]);
+
+        // pairs involving the point at infinity do not change the product
+        let mut output = [0u8; 32];
+        pairing
+            .execute(&[0u8; 384][..], &mut BytesRef::Fixed(&mut output[..]))
+            .expect("Builtin should not fail");
+        assert_eq!(output[31], 1);
+    }
+
+    #[test]
+    fn p256_verify() {
+        let f = Builtin {
+            pricer: map![0 => Pricing::Linear(Linear { base: 3450, word: 0 })],
+            native: EthereumBuiltin::from_str("p256_verify").unwrap(),
+        };
+
+        let mut input = hex!(
+            "
+			9e29650643a368c7194c56d24010641ede060c401dc3c94afc62e2dfebd6db2e
+			6c78989bd1b82b35ed275441d5d71625fd3780fce65d1e9bf095a4e9a8730976
+			d5bef4a9ee2c3bc10a2972a335b2c32571bd914302ca8f9c346a975521f16027
+			9fad84aeae08bbef7f010014d82cef6a09de2b0cf871b5ce0c4f1d13a59a5934
+			07cb45769f1070e2c2470fe5b1bfe63133c0b0cdc64ea4bf3791a8ec2a07fd4f
+		"
+        );
+
+        let mut output = [0u8; 32];
+        f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..]))
+            .expect("Builtin should not fail");
+        assert_eq!(
+            output,
+            hex!("0000000000000000000000000000000000000000000000000000000000000001")
+        );
+
+        // a different message hash does not verify and returns nothing
+        input[0] ^= 1;
+        let mut output = [0u8; 32];
+        f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..]))
+            .expect("Builtin should not fail");
+        assert_eq!(output, [0u8; 32]);
+
+        // malformed input returns nothing as well
+        let mut output = [0u8; 32];
+        f.execute(&input[..159], &mut BytesRef::Fixed(&mut output[..]))
+            .expect("Builtin should not fail");
+        assert_eq!(output, [0u8; 32]);
+    }
+
+    #[test]
+    fn message_passer() {
+        use super::{Address, MessagePasser, H256};
+
+        let f = Builtin {
+            pricer: map![0 => Pricing::Linear(Linear { base: 45000, word: 300 })],
+            native: EthereumBuiltin::from_str("message_passer").unwrap(),
+        };
+        let passer = match f.native {
+            EthereumBuiltin::MessagePasser(ref passer) => passer,
+            _ => panic!("invalid precompile type"),
+        };
+        let sender = Address::repeat_byte(0x22);
+        let mut input = vec![0x11; 20];
+        input.extend_from_slice(b"hello");
+
+        let first = passer.pass(&input, sender, 0, H256::zero()).unwrap();
+        assert_eq!(first.index, 0);
+        assert_eq!(first.target, Address::repeat_byte(0x11));
+        assert_eq!(first.message, b"hello".to_vec());
+        assert_eq!(first.topics()[0], MessagePasser::event_id());
+        assert_eq!(first.topics()[1], H256::from_low_u64_be(0));
+        assert_eq!(&first.data()[..32], first.root.as_bytes());
+        assert_eq!(first.data().len(), 128);
+        assert_eq!(&first.data()[96..101], b"hello");
+
+        // the root commits to the previous root and the message index
+        let second = passer.pass(&input, sender, 1, first.root).unwrap();
+        assert_ne!(second.root, first.root);
+        assert_ne!(passer.pass(&input, sender, 1, H256::zero()).unwrap().root, second.root);
+
+        assert!(passer.pass(&input[..19], sender, 0, H256::zero()).is_err());
+        assert!(passer.pass(&input, sender, u64::max_value(), H256::zero()).is_err());
+        assert_eq!(f.cost(&input, 0), U256::from(45000 + 300));
+
+        // without state access the call fails
+        let mut output = [0u8; 32];
+        assert!(f.execute(&input, &mut BytesRef::Fixed(&mut output[..])).is_err());
+    }
+
     #[test]
     fn bls12_381_g1_multiexp_init_from_spec() {
         use ethjson::spec::builtin::{Bls12G1Multiexp, Pricing};
@@ -2701,7 +2933,7 @@ mod tests {
         let eip198pricer = ModexpPricer { divisor: 20 };
         let eip2565pricer = Modexp2565Pricer {};
         for (name, eip198gas, eip2565gas, input) in testvectors {
-            let input = input.from_hex().unwrap();
+            let input = hex::decode(input).unwrap();
             assert_eq!(
                 eip198pricer.cost(&input).as_u64(),
                 U256::from(eip198gas).low_u64(),
@@ -162,6 +162,9 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
                 };
                 Request::Gas(gas.into())
             }
+            instructions::TLOAD | instructions::TSTORE => {
+                Request::Gas(Gas::from(vm::schedule::EIP1153_TRANSIENT_STORAGE_GAS))
+            }
             instructions::BALANCE => {
                 let address = u256_to_address(stack.peek(0));
                 Request::Gas(accessed_addresses_gas(&address, schedule.balance_gas))
@@ -221,6 +224,14 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
                     Gas::from_u256(*stack.peek(2))?,
                 )
             }
+            instructions::MCOPY => Request::GasMemCopy(
+                default_gas,
+                cmp::max(
+                    mem_needed(stack.peek(0), stack.peek(2))?,
+                    mem_needed(stack.peek(1), stack.peek(2))?,
+                ),
+                Gas::from_u256(*stack.peek(2))?,
+            ),
             instructions::EXTCODECOPY => {
                 let address = u256_to_address(stack.peek(0));
                 let gas = accessed_addresses_gas(&address, schedule.extcodecopy_base_gas);
@@ -255,6 +266,12 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
 
                 let address = u256_to_address(stack.peek(1));
                 gas = accessed_addresses_gas(&address, gas.as_usize());
+                if let Some(target) = ext.delegation(&address)? {
+                    gas = overflowing!(gas.overflow_add(accessed_addresses_gas(
+                        &target,
+                        schedule.cold_account_access_cost
+                    )));
+                }
 
                 let is_value_transfer = !stack.peek(2).is_zero();
 
@@ -279,6 +296,12 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
                 let mut gas = Gas::from(schedule.call_gas);
                 let address = u256_to_address(stack.peek(1));
                 gas = accessed_addresses_gas(&address, gas.as_usize());
+                if let Some(target) = ext.delegation(&address)? {
+                    gas = overflowing!(gas.overflow_add(accessed_addresses_gas(
+                        &target,
+                        schedule.cold_account_access_cost
+                    )));
+                }
 
                 let mem = cmp::max(
                     mem_needed(stack.peek(4), stack.peek(5))?,
@@ -292,7 +315,8 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
                 let start = stack.peek(1);
                 let len = stack.peek(2);
 
-                let gas = Gas::from(schedule.create_gas);
+                let base = Gas::from(schedule.create_gas);
+                let gas = overflowing!(base.overflow_add(initcode_gas(schedule, len)?));
                 let mem = mem_needed(start, len)?;
 
                 Request::GasMemProvide(gas, mem, None)
@@ -301,7 +325,9 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
                 let start = stack.peek(1);
                 let len = stack.peek(2);
 
-                let base = Gas::from(schedule.create_gas);
+                let base = overflowing!(
+                    Gas::from(schedule.create_gas).overflow_add(initcode_gas(schedule, len)?)
+                );
                 let word = overflowing!(to_word_size(Gas::from_u256(*len)?));
                 let word_gas = overflowing!(Gas::from(schedule.sha3_word_gas).overflow_mul(word));
                 let gas = overflowing!(base.overflow_add(word_gas));
@@ -416,6 +442,21 @@ fn add_gas_usize<Gas: evm::CostType>(value: Gas, num: usize) -> (Gas, bool) {
     value.overflow_add(Gas::from(num))
 }
 
+/// Gas charged for the init code of `CREATE` and `CREATE2` as by EIP-3860.
+///
+/// Init code above `max_initcode_size` is an exceptional abort, consuming all gas.
+#[inline]
+fn initcode_gas<Gas: evm::CostType>(schedule: &Schedule, len: &U256) -> vm::Result<Gas> {
+    if !schedule.eip3860 {
+        return Ok(Gas::from(0));
+    }
+    if *len > U256::from(schedule.max_initcode_size) {
+        return Err(vm::Error::OutOfGas);
+    }
+    let word = overflowing!(to_word_size(Gas::from_u256(*len)?));
+    Ok(overflowing!(Gas::from(schedule.initcode_word_gas).overflow_mul(word)))
+}
+
 #[inline]
 fn to_word_size<Gas: evm::CostType>(value: Gas) -> (Gas, bool) {
     let (gas, overflow) = add_gas_usize(value, 31);
@@ -35,6 +35,8 @@ pub trait Memory {
     fn read(&self, offset: U256) -> U256;
     /// Write slice of bytes to memory. Does not resize memory!
     fn write_slice(&mut self, offset: U256, slice: &[u8]);
+    /// Copy `size` bytes within memory from `src` to `dest`, allowing overlap. Does not resize memory!
+    fn copy_within(&mut self, dest: U256, src: U256, size: U256);
     /// Retrieve part of the memory between offset and offset + size
     fn read_slice(&self, offset: U256, size: U256) -> &[u8];
     /// Retrieve writeable part of memory
@@ -87,6 +89,15 @@ impl Memory for Vec<u8> {
         }
     }
 
+    fn copy_within(&mut self, dest: U256, src: U256, size: U256) {
+        let size = size.low_u64() as usize;
+        if size > 0 {
+            let src = src.low_u64() as usize;
+            let dest = dest.low_u64() as usize;
+            <[u8]>::copy_within(self, src..src + size, dest);
+        }
+    }
+
     fn write(&mut self, offset: U256, value: U256) {
         let off = offset.low_u64() as usize;
         value.to_big_endian(&mut self[off..off + 32]);
@@ -189,4 +200,21 @@ mod tests {
             assert_eq!(mem.size(), 32);
         }
     }
+
+    #[test]
+    fn test_memory_copy_within_overlapping() {
+        let mem: &mut dyn Memory = &mut vec![];
+        mem.resize(32);
+        mem.write_slice(U256::from(0), "0123456789".as_bytes());
+
+        mem.copy_within(U256::from(2), U256::from(0), U256::from(8));
+        assert_eq!(mem.read_slice(U256::from(0), U256::from(10)), "0101234567".as_bytes());
+
+        mem.copy_within(U256::from(0), U256::from(2), U256::from(8));
+        assert_eq!(mem.read_slice(U256::from(0), U256::from(10)), "0123456767".as_bytes());
+
+        // zero size copies are no-ops even out of bounds
+        mem.copy_within(U256::from(0x1000), U256::from(0x2000), U256::from(0));
+        assert_eq!(mem.size(), 32);
+    }
 }
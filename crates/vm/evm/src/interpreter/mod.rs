@@ -63,10 +63,6 @@ const TWO_POW_96: U256 = U256([0, 0x100000000, 0, 0]); //0x1 00000000 00000000 0
 const TWO_POW_224: U256 = U256([0, 0, 0, 0x100000000]); //0x1 00000000 00000000 00000000 00000000 00000000 00000000 00000000
 const TWO_POW_248: U256 = U256([0, 0, 0, 0x100000000000000]); //0x1 00000000 00000000 00000000 00000000 00000000 00000000 00000000 000000
 
-/// Maximum subroutine stack size as specified in
-/// https://eips.ethereum.org/EIPS/eip-2315.
-pub const MAX_SUB_STACK_SIZE: usize = 1023;
-
 fn to_biguint(x: U256) -> BigUint {
     let mut bytes = [0u8; 32];
     x.to_little_endian(&mut bytes);
@@ -107,8 +103,6 @@ enum InstructionResult<Gas> {
     Ok,
     UnusedGas(Gas),
     JumpToPosition(U256),
-    JumpToSubroutine(U256),
-    ReturnFromSubroutine(usize),
     StopExecutionNeedsReturn {
         /// Gas left.
         gas: Gas,
@@ -181,10 +175,8 @@ pub struct Interpreter<Cost: CostType> {
     do_trace: bool,
     done: bool,
     valid_jump_destinations: Option<Arc<BitSet>>,
-    valid_subroutine_destinations: Option<Arc<BitSet>>,
     gasometer: Option<Gasometer<Cost>>,
     stack: VecStack<U256>,
-    return_stack: Vec<usize>,
     resume_output_range: Option<(U256, U256)>,
     resume_result: Option<InstructionResult<Cost>>,
     last_stack_ret_len: usize,
@@ -287,10 +279,8 @@ impl<Cost: CostType> Interpreter<Cost> {
         let params = InterpreterParams::from(params);
         let informant = informant::EvmInformant::new(depth);
         let valid_jump_destinations = None;
-        let valid_subroutine_destinations = None;
         let gasometer = Cost::from_u256(params.gas).ok().map(|gas| Gasometer::<Cost>::new(gas));
         let stack = VecStack::with_capacity(schedule.stack_limit, U256::zero());
-        let return_stack = Vec::with_capacity(MAX_SUB_STACK_SIZE);
 
         Interpreter {
             cache,
@@ -298,10 +288,8 @@ impl<Cost: CostType> Interpreter<Cost> {
             reader,
             informant,
             valid_jump_destinations,
-            valid_subroutine_destinations,
             gasometer,
             stack,
-            return_stack,
             done: false,
             // Overridden in `step_inner` based on
             // the result of `ext.trace_next_instruction`.
@@ -469,9 +457,7 @@ impl<Cost: CostType> Interpreter<Cost> {
             InstructionResult::JumpToPosition(position) => {
                 if self.valid_jump_destinations.is_none() {
                     self.valid_jump_destinations = Some(
-                        self.cache
-                            .jump_and_sub_destinations(&self.params.code_hash, &self.reader.code)
-                            .0,
+                        self.cache.jump_destinations(&self.params.code_hash, &self.reader.code),
                     );
                 }
                 let jump_destinations = self
@@ -484,28 +470,6 @@ impl<Cost: CostType> Interpreter<Cost> {
                 };
                 self.reader.position = pos;
             }
-            InstructionResult::JumpToSubroutine(position) => {
-                if self.valid_subroutine_destinations.is_none() {
-                    self.valid_subroutine_destinations = Some(
-                        self.cache
-                            .jump_and_sub_destinations(&self.params.code_hash, &self.reader.code)
-                            .1,
-                    );
-                }
-                let subroutine_destinations = self
-                    .valid_subroutine_destinations
-                    .as_ref()
-                    .expect("subroutine_destinations are initialized on first jump; qed");
-                let pos = match self.verify_jump(position, subroutine_destinations) {
-                    Ok(x) => x,
-                    Err(e) => return InterpreterResult::Done(Err(e)),
-                };
-                self.return_stack.push(self.reader.position);
-                self.reader.position = pos + 1;
-            }
-            InstructionResult::ReturnFromSubroutine(pos) => {
-                self.reader.position = pos;
-            }
             InstructionResult::StopExecutionNeedsReturn { gas, init_off, init_size, apply } => {
                 let mem = mem::replace(&mut self.mem, Vec::new());
                 return InterpreterResult::Done(Ok(GasLeft::NeedsReturn {
@@ -549,8 +513,9 @@ impl<Cost: CostType> Interpreter<Cost> {
             || (instruction == CHAINID && !schedule.have_chain_id)
             || (instruction == SELFBALANCE && !schedule.have_selfbalance)
             || (instruction == BASEFEE && !schedule.eip3198)
-            || ((instruction == BEGINSUB || instruction == JUMPSUB || instruction == RETURNSUB)
-                && !schedule.have_subs)
+            || (instruction == PUSH0 && !schedule.have_push0)
+            || ((instruction == TLOAD || instruction == TSTORE) && !schedule.have_transient_storage)
+            || (instruction == MCOPY && !schedule.have_mcopy)
         {
             return Err(vm::Error::BadInstruction { instruction: instruction as u8 });
         }
@@ -581,6 +546,7 @@ impl<Cost: CostType> Interpreter<Cost> {
                 Some((read(0), read(2)))
             }
             instructions::EXTCODECOPY => Some((read(1), read(3))),
+            instructions::MCOPY => Some((read(0), read(2))),
             instructions::CALL | instructions::CALLCODE => Some((read(5), read(6))),
             instructions::DELEGATECALL | instructions::STATICCALL => Some((read(4), read(5))),
             _ => None,
@@ -618,23 +584,6 @@ impl<Cost: CostType> Interpreter<Cost> {
             instructions::JUMPDEST => {
                 // ignore
             }
-            instructions::BEGINSUB => {
-                return Err(vm::Error::InvalidSubEntry);
-            }
-            instructions::JUMPSUB => {
-                if self.return_stack.len() >= MAX_SUB_STACK_SIZE {
-                    return Err(vm::Error::OutOfSubStack { wanted: 1, limit: MAX_SUB_STACK_SIZE });
-                }
-                let sub_destination = self.stack.pop_back();
-                return Ok(InstructionResult::JumpToSubroutine(sub_destination));
-            }
-            instructions::RETURNSUB => {
-                if let Some(pos) = self.return_stack.pop() {
-                    return Ok(InstructionResult::ReturnFromSubroutine(pos));
-                } else {
-                    return Err(vm::Error::SubStackUnderflow { wanted: 1, on_stack: 0 });
-                }
-            }
             instructions::CREATE | instructions::CREATE2 => {
                 let endowment = self.stack.pop_back();
                 let init_off = self.stack.pop_back();
@@ -741,6 +690,9 @@ impl<Cost: CostType> Interpreter<Cost> {
                     .0;
 
                 ext.al_insert_address(code_address);
+                if let Some(target) = ext.delegation(&code_address)? {
+                    ext.al_insert_address(target);
+                }
 
                 // Get sender & receive addresses, check if we have balance
                 let (sender_address, receive_address, has_balance, call_type) = match instruction {
@@ -874,6 +826,9 @@ impl<Cost: CostType> Interpreter<Cost> {
                     self.stack.pop_n(no_of_topics).iter().map(BigEndianHash::from_uint).collect();
                 ext.log(topics, self.mem.read_slice(offset, size))?;
             }
+            instructions::PUSH0 => {
+                self.stack.push(U256::zero());
+            }
             instructions::PUSH1
             | instructions::PUSH2
             | instructions::PUSH3
@@ -965,6 +920,22 @@ impl<Cost: CostType> Interpreter<Cost> {
                 ext.set_storage(key, BigEndianHash::from_uint(&val))?;
                 ext.al_insert_storage_key(self.params.address, key);
             }
+            instructions::TLOAD => {
+                let key = BigEndianHash::from_uint(&self.stack.pop_back());
+                let word = ext.transient_storage_at(&key)?.into_uint();
+                self.stack.push(word);
+            }
+            instructions::TSTORE => {
+                let key = BigEndianHash::from_uint(&self.stack.pop_back());
+                let val = self.stack.pop_back();
+                ext.set_transient_storage(key, BigEndianHash::from_uint(&val))?;
+            }
+            instructions::MCOPY => {
+                let dest = self.stack.pop_back();
+                let src = self.stack.pop_back();
+                let size = self.stack.pop_back();
+                Memory::copy_within(&mut self.mem, dest, src, size);
+            }
             instructions::PC => {
                 self.stack.push(U256::from(self.reader.position - 1));
             }
@@ -1465,8 +1436,8 @@ fn address_to_u256(value: Address) -> U256 {
 mod tests {
     use crate::factory::Factory;
     use crate::vmtype::VMType;
+    use crate::tests::FromHex;
     use ethereum_types::Address;
-    use hex::FromHex;
     use std::sync::Arc;
     use vm::tests::{test_finalize, FakeExt};
     use vm::{self, ActionParams, ActionValue, Exec};
@@ -27,7 +27,6 @@ struct Bits(Arc<BitSet>);
 #[derive(Clone)]
 struct CacheItem {
     jump_destination: Bits,
-    sub_entrypoint: Bits,
 }
 
 /// Global cache for EVM interpreter
@@ -41,16 +40,12 @@ impl SharedCache {
     }
 
     /// Get jump destinations bitmap for a contract.
-    pub fn jump_and_sub_destinations(
-        &self, _code_hash: &Option<H256>, code: &[u8],
-    ) -> (Arc<BitSet>, Arc<BitSet>) {
-        let d = Self::find_jump_and_sub_destinations(code);
-        (d.jump_destination.0, d.sub_entrypoint.0)
+    pub fn jump_destinations(&self, _code_hash: &Option<H256>, code: &[u8]) -> Arc<BitSet> {
+        Self::find_jump_destinations(code).jump_destination.0
     }
 
-    fn find_jump_and_sub_destinations(code: &[u8]) -> CacheItem {
+    fn find_jump_destinations(code: &[u8]) -> CacheItem {
         let mut jump_dests = BitSet::with_capacity(code.len());
-        let mut sub_entrypoints = BitSet::with_capacity(code.len());
         let mut position = 0;
 
         while position < code.len() {
@@ -61,9 +56,6 @@ impl SharedCache {
                     instructions::JUMPDEST => {
                         jump_dests.insert(position);
                     }
-                    instructions::BEGINSUB => {
-                        sub_entrypoints.insert(position);
-                    }
                     _ => {
                         if let Some(push_bytes) = instruction.push_bytes() {
                             position += push_bytes;
@@ -75,10 +67,7 @@ impl SharedCache {
         }
 
         jump_dests.shrink_to_fit();
-        CacheItem {
-            jump_destination: Bits(Arc::new(jump_dests)),
-            sub_entrypoint: Bits(Arc::new(sub_entrypoints)),
-        }
+        CacheItem { jump_destination: Bits(Arc::new(jump_dests)) }
     }
 }
 
@@ -106,11 +95,10 @@ mod test {
         let code = hex!("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff5b01600055");
 
         // when
-        let cache_item = SharedCache::find_jump_and_sub_destinations(&code);
+        let cache_item = SharedCache::find_jump_destinations(&code);
 
         // then
         assert!(cache_item.jump_destination.0.iter().eq(vec![66].into_iter()));
-        assert!(cache_item.sub_entrypoint.0.is_empty());
     }
 
     #[test]
@@ -127,30 +115,29 @@ mod test {
         let code = hex!("600656605B565B6004");
 
         // when
-        let cache_item = SharedCache::find_jump_and_sub_destinations(&code);
+        let cache_item = SharedCache::find_jump_destinations(&code);
 
         // then
         assert!(cache_item.jump_destination.0.iter().eq(vec![6].into_iter()));
-        assert!(cache_item.sub_entrypoint.0.is_empty());
     }
 
     #[test]
     fn test_find_sub_entrypoints() {
         // given
 
-        // see https://eips.ethereum.org/EIPS/eip-2315 for disassembly
+        // see https://eips.ethereum.org/EIPS/eip-2315 for disassembly, the former BEGINSUB
+        // bytes are TLOAD since Cancun and mark no destination
         let code = hex!("6800000000000000000c5e005c60115e5d5c5d");
 
         // when
-        let cache_item = SharedCache::find_jump_and_sub_destinations(&code);
+        let cache_item = SharedCache::find_jump_destinations(&code);
 
         // then
         assert!(cache_item.jump_destination.0.is_empty());
-        assert!(cache_item.sub_entrypoint.0.iter().eq(vec![12, 17].into_iter()));
     }
 
     #[test]
-    fn test_find_jump_and_sub_allowing_unknown_opcodes() {
+    fn test_find_jump_allowing_unknown_opcodes() {
         // precondition
         assert!(Instruction::from_u8(0xcc) == None);
 
@@ -158,14 +145,13 @@ mod test {
 
         // 0000 5B   JUMPDEST
         // 0001 CC   ???
-        // 0002 5C   BEGINSUB
-        let code = hex!("5BCC5C");
+        // 0002 5B   JUMPDEST
+        let code = hex!("5BCC5B");
 
         // when
-        let cache_item = SharedCache::find_jump_and_sub_destinations(&code);
+        let cache_item = SharedCache::find_jump_destinations(&code);
 
         // then
-        assert!(cache_item.jump_destination.0.iter().eq(vec![0].into_iter()));
-        assert!(cache_item.sub_entrypoint.0.iter().eq(vec![2].into_iter()));
+        assert!(cache_item.jump_destination.0.iter().eq(vec![0, 2].into_iter()));
     }
 }
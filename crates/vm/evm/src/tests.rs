@@ -14,12 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::interpreter::MAX_SUB_STACK_SIZE;
 use crate::factory::Factory;
 use crate::vmtype::VMType;
 use ethereum_types::{Address, H256, U256};
 use hashbrown::{HashMap, HashSet};
-use hex::FromHex;
+use hex::FromHexError;
+
+/// Decoding of hex strings in tests, `hex::FromHex` has no method on `str`.
+pub(crate) trait FromHex {
+    fn from_hex(&self) -> Result<Vec<u8>, FromHexError>;
+}
+
+impl FromHex for str {
+    fn from_hex(&self) -> Result<Vec<u8>, FromHexError> {
+        hex::decode(self)
+    }
+}
 use hex_literal::hex;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -426,6 +436,153 @@ fn test_base_fee(factory: super::Factory) {
     assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000007");
 }
 
+evm_test! {test_push0: test_push0_int}
+fn test_push0(factory: super::Factory) {
+    let code = hex!("60425f55").to_vec();
+
+    let mut params = ActionParams::default();
+    params.gas = U256::from(100_000);
+    params.code = Some(Arc::new(code));
+    let mut ext = FakeExt::new_shanghai(Address::zero(), Address::zero(), &[]);
+
+    let gas_left = {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
+    };
+
+    assert_eq!(gas_left, U256::from(77_895));
+    assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000042");
+}
+
+#[test]
+fn test_push0_before_shanghai_int() {
+    let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+    let code = hex!("5f").to_vec();
+
+    let mut params = ActionParams::default();
+    params.gas = U256::from(100_000);
+    params.code = Some(Arc::new(code));
+    let mut ext = FakeExt::new_london(Address::zero(), Address::zero(), &[]);
+
+    let err = {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap_err()
+    };
+
+    assert_eq!(err, vm::Error::BadInstruction { instruction: 0x5f });
+}
+
+evm_test! {test_transient_storage: test_transient_storage_int}
+fn test_transient_storage(factory: super::Factory) {
+    // TSTORE 0x42 at key 1, TLOAD it back and persist it at key 0
+    let code = hex!("604260015d60015c5f55").to_vec();
+
+    let mut params = ActionParams::default();
+    params.gas = U256::from(100_000);
+    params.code = Some(Arc::new(code));
+    let mut ext = FakeExt::new_cancun(Address::zero(), Address::zero(), &[]);
+
+    let gas_left = {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
+    };
+
+    assert_eq!(gas_left, U256::from(77_689));
+    assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000042");
+    assert_eq!(
+        ext.transient_store.get(&H256::from_low_u64_be(1)),
+        Some(&H256::from_low_u64_be(0x42))
+    );
+}
+
+#[test]
+fn test_tload_before_cancun_int() {
+    let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+    let code = hex!("5f5c").to_vec();
+
+    let mut params = ActionParams::default();
+    params.gas = U256::from(100_000);
+    params.code = Some(Arc::new(code));
+    let mut ext = FakeExt::new_shanghai(Address::zero(), Address::zero(), &[]);
+
+    let err = {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap_err()
+    };
+
+    assert_eq!(err, vm::Error::BadInstruction { instruction: 0x5c });
+}
+
+evm_test! {test_mcopy: test_mcopy_int}
+fn test_mcopy(factory: super::Factory) {
+    // MSTORE 0x42 at 0, MCOPY the word to 32, MLOAD it and persist it at key 0
+    let code = hex!("60425f5260205f60205e6020515f55").to_vec();
+
+    let mut params = ActionParams::default();
+    params.gas = U256::from(100_000);
+    params.code = Some(Arc::new(code));
+    let mut ext = FakeExt::new_cancun(Address::zero(), Address::zero(), &[]);
+
+    let gas_left = {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
+    };
+
+    assert_eq!(gas_left, U256::from(77_864));
+    assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000042");
+}
+
+evm_test! {test_create_initcode_word_gas: test_create_initcode_word_gas_int}
+fn test_create_initcode_word_gas(factory: super::Factory) {
+    // CREATE with 64 bytes of init code, charged 2 words of EIP-3860 init code gas
+    let code = hex!("60405f5ff000").to_vec();
+
+    let address = Address::from_low_u64_be(0x155);
+    let mut params = ActionParams::default();
+    params.gas = U256::from(100_000);
+    params.code = Some(Arc::new(code));
+    params.address = address.clone();
+    let mut ext = FakeExt::new_shanghai(Address::zero(), address.clone(), &[]);
+
+    {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap();
+    };
+
+    assert_set_contains(
+        &ext.calls,
+        &FakeCall {
+            call_type: FakeCallType::Create,
+            create_scheme: Some(vm::CreateContractAddress::FromSenderAndNonce),
+            gas: U256::from(66_921),
+            sender_address: None,
+            receive_address: None,
+            value: Some(U256::zero()),
+            data: vec![0; 64],
+            code_address: None,
+        },
+    );
+}
+
+evm_test! {test_create_initcode_limit: test_create_initcode_limit_int}
+fn test_create_initcode_limit(factory: super::Factory) {
+    // CREATE with init code one byte above the EIP-3860 limit
+    let code = hex!("61c0015f5ff000").to_vec();
+
+    let mut params = ActionParams::default();
+    params.gas = U256::from(1_000_000);
+    params.code = Some(Arc::new(code));
+    let mut ext = FakeExt::new_shanghai(Address::zero(), Address::zero(), &[]);
+
+    let err = {
+        let vm = factory.create(params, ext.schedule(), ext.depth());
+        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap_err()
+    };
+
+    assert_eq!(err, vm::Error::OutOfGas);
+    assert_eq!(ext.calls.len(), 0);
+}
+
 evm_test! {test_gas_limit: test_gas_limit_int}
 fn test_gas_limit(factory: super::Factory) {
     let gas_limit = U256::from(0x1234);
@@ -815,165 +972,6 @@ fn test_jumps(factory: super::Factory) {
     assert_eq!(gas_left, U256::from(54_117u32));
 }
 
-evm_test! {test_subs_simple: test_subs_simple_int}
-fn test_subs_simple(factory: super::Factory) {
-    // as defined in https://eips.ethereum.org/EIPS/eip-2315
-    let code = hex!("60045e005c5d").to_vec();
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(18u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let gas_left = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
-    };
-
-    assert_eq!(gas_left, U256::from(0u32));
-}
-
-evm_test! {test_subs_two_levels: test_subs_two_levels_int}
-fn test_subs_two_levels(factory: super::Factory) {
-    // as defined in https://eips.ethereum.org/EIPS/eip-2315
-    let code = hex!("6800000000000000000c5e005c60115e5d5c5d").to_vec();
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(36u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let gas_left = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
-    };
-
-    assert_eq!(gas_left, U256::from(0u32));
-}
-
-evm_test! {test_subs_invalid_jump: test_subs_invalid_jump_int}
-fn test_subs_invalid_jump(factory: super::Factory) {
-    // as defined in https://eips.ethereum.org/EIPS/eip-2315
-    let code = hex!("6801000000000000000c5e005c60115e5d5c5d").to_vec();
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(24u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let current = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap())
-    };
-
-    let expected = Result::Err(vm::Error::BadJumpDestination { destination: 0xc });
-    assert_eq!(current, expected);
-}
-
-evm_test! {test_subs_shallow_return_stack: test_subs_shallow_return_stack_int}
-fn test_subs_shallow_return_stack(factory: super::Factory) {
-    // as defined in https://eips.ethereum.org/EIPS/eip-2315
-    let code = hex!("5d5858").to_vec();
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(24u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let current = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap())
-    };
-
-    let expected = Result::Err(vm::Error::SubStackUnderflow { wanted: 1, on_stack: 0 });
-    assert_eq!(current, expected);
-}
-
-evm_test! {test_subs_substack_limit: test_subs_substack_limit_int}
-fn test_subs_substack_limit(factory: super::Factory) {
-    //    PUSH <recursion_limit>
-    //    JUMP a
-    // s: BEGINSUB
-    // a: JUMPDEST
-    //    DUP1
-    //    JUMPI c
-    //    STOP
-    // c: JUMPDEST
-    //    PUSH1 1
-    //    SWAP
-    //    SUB
-    //    JUMPSUB s
-
-    let mut code = hex!("6104006007565c5b80600d57005b6001900360065e").to_vec();
-    code[1..3].copy_from_slice(&(MAX_SUB_STACK_SIZE as u16).to_be_bytes()[..]);
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(1_000_000u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let gas_left = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
-    };
-
-    assert_eq!(gas_left, U256::from(959_049u32));
-}
-
-evm_test! {test_subs_substack_out: test_subs_substack_out_int}
-fn test_subs_substack_out(factory: super::Factory) {
-    let mut code = hex!("6104006007565c5b80600d57005b6001900360065e").to_vec();
-    code[1..3].copy_from_slice(&((MAX_SUB_STACK_SIZE + 1) as u16).to_be_bytes()[..]);
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(1_000_000u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let current = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap())
-    };
-
-    let expected = Result::Err(vm::Error::OutOfSubStack { wanted: 1, limit: MAX_SUB_STACK_SIZE });
-    assert_eq!(current, expected);
-}
-
-evm_test! {test_subs_sub_at_end: test_subs_sub_at_end_int}
-fn test_subs_sub_at_end(factory: super::Factory) {
-    let code = hex!("6005565c5d5b60035e").to_vec();
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(30u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let gas_left = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
-    };
-
-    assert_eq!(gas_left, U256::from(0u32));
-}
-
-evm_test! {test_subs_walk_into_subroutine: test_subs_walk_into_subroutine_int}
-fn test_subs_walk_into_subroutine(factory: super::Factory) {
-    let code = hex!("5c5d00").to_vec();
-
-    let mut params = ActionParams::default();
-    params.gas = U256::from(100u32);
-    params.code = Some(Arc::new(code));
-    let mut ext = FakeExt::new_berlin(Address::zero(), Address::zero(), &[]);
-
-    let current = {
-        let vm = factory.create(params, ext.schedule(), ext.depth());
-        test_finalize(vm.exec(&mut ext).ok().unwrap())
-    };
-
-    let expected = Result::Err(vm::Error::InvalidSubEntry);
-    assert_eq!(current, expected);
-}
-
 evm_test! {test_calls: test_calls_int}
 fn test_calls(factory: super::Factory) {
     let code = "600054602d57600160005560006000600060006050610998610100f160006000600060006050610998610100f25b".from_hex().unwrap();
@@ -0,0 +1,68 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of OpenEthereum.
+
+// OpenEthereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// OpenEthereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with OpenEthereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-7702 delegation designators.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use ethereum_types::Address;
+
+/// Prefix of the code an EIP-7702 authorization installs on its authority.
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+/// Length of a delegation designator, the prefix followed by the target address.
+pub const DELEGATION_DESIGNATOR_LEN: usize = 23;
+
+/// Code that delegates execution of an account to `target`.
+pub fn designator(target: &Address) -> Vec<u8> {
+    let mut code = Vec::with_capacity(DELEGATION_DESIGNATOR_LEN);
+    code.extend_from_slice(&DELEGATION_PREFIX);
+    code.extend_from_slice(target.as_bytes());
+    code
+}
+
+/// Returns the delegation target if `code` is a delegation designator.
+pub fn target(code: &[u8]) -> Option<Address> {
+    if code.len() == DELEGATION_DESIGNATOR_LEN && code.starts_with(&DELEGATION_PREFIX) {
+        Some(Address::from_slice(&code[DELEGATION_PREFIX.len()..]))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_designator() {
+        let address = Address::from_low_u64_be(0xc0de);
+        let code = designator(&address);
+        assert_eq!(code.len(), DELEGATION_DESIGNATOR_LEN);
+        assert_eq!(target(&code), Some(address));
+    }
+
+    #[test]
+    fn should_not_parse_other_code() {
+        let code = designator(&Address::from_low_u64_be(0xc0de));
+        assert_eq!(target(&code[..22]), None);
+        let mut longer = code.clone();
+        longer.push(0);
+        assert_eq!(target(&longer), None);
+        assert_eq!(target(&[&[0xef, 0x01, 0x01], &code[3..]].concat()), None);
+        assert_eq!(target(&[]), None);
+    }
+}
@@ -78,6 +78,12 @@ pub trait Ext {
     /// Stores a value for given key.
     fn set_storage(&mut self, key: H256, value: H256) -> Result<()>;
 
+    /// Returns a transient value for given key.
+    fn transient_storage_at(&self, key: &H256) -> Result<H256>;
+
+    /// Stores a transient value for given key, discarded at the end of the transaction.
+    fn set_transient_storage(&mut self, key: H256, value: H256) -> Result<()>;
+
     /// Determine whether an account exists.
     fn exists(&self, address: &Address) -> Result<bool>;
 
@@ -123,6 +129,9 @@ pub trait Ext {
     /// Returns code size at given address
     fn extcodesize(&self, address: &Address) -> Result<Option<usize>>;
 
+    /// Returns the EIP-7702 delegation target of the code at given address
+    fn delegation(&self, address: &Address) -> Result<Option<Address>>;
+
     /// Creates log entry with given topics and data
     fn log(&mut self, topics: Vec<H256>, data: &[u8]) -> Result<()>;
 
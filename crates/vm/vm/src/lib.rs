@@ -35,6 +35,7 @@ extern crate rlp;
 pub mod access_list;
 mod action_params;
 mod call_type;
+pub mod delegation;
 mod env_info;
 mod error;
 mod ext;
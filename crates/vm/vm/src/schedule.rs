@@ -34,6 +34,14 @@ pub const EIP3529_MAX_REFUND_QUOTIENT: usize = 5;
 /// Reduced SSTORE refund as by EIP-3529
 pub const EIP3529_SSTORE_CLEARS_SCHEDULE: usize =
     EIP2929_SSTORE_RESET_GAS + EIP2930_ACCESS_LIST_STORAGE_KEY_COST;
+/// Gas per word of init code as by EIP-3860
+pub const EIP3860_INITCODE_WORD_GAS: usize = 2;
+/// Gas per TLOAD and TSTORE as by EIP-1153
+pub const EIP1153_TRANSIENT_STORAGE_GAS: usize = 100;
+/// Intrinsic gas per EIP-7702 authorization
+pub const EIP7702_PER_EMPTY_ACCOUNT_COST: usize = 25000;
+/// Refund per EIP-7702 authorization whose authority already exists
+pub const EIP7702_PER_AUTH_BASE_COST: usize = 12500;
 
 /// Definition of the cost schedule and other parameterisations for the EVM.
 #[derive(Debug)]
@@ -112,6 +120,12 @@ pub struct Schedule {
     pub tx_data_zero_gas: usize,
     /// Additional cost for non-empty data transaction
     pub tx_data_non_zero_gas: usize,
+    /// Multiplier of the transaction data gas
+    pub tx_data_gas_factor: usize,
+    /// Bytes of transaction envelope charged on top of the data as non-zero bytes
+    pub tx_base_size: usize,
+    /// Charge transaction data by its estimated compressed size
+    pub tx_data_compressed: bool,
     /// Gas price for copying memory
     pub copy_gas: usize,
     /// Price of EXTCODESIZE
@@ -145,8 +159,6 @@ pub struct Schedule {
     pub have_chain_id: bool,
     /// SELFBALANCE opcode enabled.
     pub have_selfbalance: bool,
-    /// BEGINSUB, JUMPSUB and RETURNSUB opcodes enabled.
-    pub have_subs: bool,
     /// Kill basic accounts below this balance if touched.
     pub kill_dust: CleanDustMode,
     /// Enable EIP-1283 rules
@@ -171,6 +183,26 @@ pub struct Schedule {
     pub max_refund_quotient: usize,
     // Enable EIP-3541 rule
     pub eip3541: bool,
+    /// PUSH0 opcode enabled.
+    pub have_push0: bool,
+    /// Enable EIP-3860 rules, init code is limited in size and charged per word
+    pub eip3860: bool,
+    /// Maximum init code size when creating a contract.
+    pub max_initcode_size: usize,
+    /// Additional gas for each word of init code when creating a contract
+    pub initcode_word_gas: usize,
+    /// Enable EIP-3651 rule, the coinbase address starts warm
+    pub eip3651: bool,
+    /// TLOAD and TSTORE opcodes enabled.
+    pub have_transient_storage: bool,
+    /// MCOPY opcode enabled.
+    pub have_mcopy: bool,
+    /// Enable EIP-6780 rule, SELFDESTRUCT only destroys contracts created in the same transaction
+    pub eip6780: bool,
+    /// Enable EIP-7702 rules, set-code transactions and delegated account code
+    pub eip7702: bool,
+    /// Enable EIP-2935 rules, BLOCKHASH served from the history contract storage
+    pub eip2935: bool,
 }
 
 /// Dust accounts cleanup mode.
@@ -208,7 +240,6 @@ impl Schedule {
             have_bitwise_shifting: false,
             have_chain_id: false,
             have_selfbalance: false,
-            have_subs: false,
             have_extcodehash: false,
             stack_limit: 1024,
             max_depth: 1024,
@@ -242,6 +273,9 @@ impl Schedule {
             tx_create_gas: 53000,
             tx_data_zero_gas: 4,
             tx_data_non_zero_gas: 68,
+            tx_data_gas_factor: 1,
+            tx_base_size: 0,
+            tx_data_compressed: false,
             copy_gas: 3,
             extcodesize_gas: 700,
             extcodecopy_base_gas: 700,
@@ -266,6 +300,16 @@ impl Schedule {
             eip3198: false,
             max_refund_quotient: MAX_REFUND_QUOTIENT,
             eip3541: false,
+            have_push0: false,
+            eip3860: false,
+            max_initcode_size: max_code_size.saturating_mul(2),
+            initcode_word_gas: 0,
+            eip3651: false,
+            have_transient_storage: false,
+            have_mcopy: false,
+            eip6780: false,
+            eip7702: false,
+            eip2935: false,
         }
     }
 
@@ -302,7 +346,6 @@ impl Schedule {
     /// Schedule for the Yolov3 testnet of the Ethereum main net.
     pub fn new_berlin() -> Schedule {
         let mut schedule = Self::new_istanbul();
-        schedule.have_subs = true; // EIP 2315
 
         schedule.eip1283 = true;
         schedule.eip2929 = true;
@@ -340,6 +383,39 @@ impl Schedule {
         schedule
     }
 
+    /// Schedule for the Shanghai fork of the Ethereum main net.
+    pub fn new_shanghai() -> Schedule {
+        let mut schedule = Self::new_london();
+
+        schedule.have_push0 = true; // EIP 3855
+        schedule.eip3860 = true;
+        schedule.initcode_word_gas = EIP3860_INITCODE_WORD_GAS;
+        schedule.eip3651 = true;
+
+        schedule
+    }
+
+    /// Schedule for the Cancun fork of the Ethereum main net.
+    pub fn new_cancun() -> Schedule {
+        let mut schedule = Self::new_shanghai();
+
+        schedule.have_transient_storage = true; // EIP 1153
+        schedule.have_mcopy = true; // EIP 5656
+        schedule.eip6780 = true;
+
+        schedule
+    }
+
+    /// Schedule for the Prague fork of the Ethereum main net.
+    pub fn new_prague() -> Schedule {
+        let mut schedule = Self::new_cancun();
+
+        schedule.eip7702 = true;
+        schedule.eip2935 = true;
+
+        schedule
+    }
+
     fn new(efcd: bool, hdc: bool, tcg: usize) -> Schedule {
         Schedule {
             exceptional_failed_code_deposit: efcd,
@@ -350,7 +426,6 @@ impl Schedule {
             have_bitwise_shifting: false,
             have_chain_id: false,
             have_selfbalance: false,
-            have_subs: false,
             have_extcodehash: false,
             stack_limit: 1024,
             max_depth: 1024,
@@ -384,6 +459,9 @@ impl Schedule {
             tx_create_gas: tcg,
             tx_data_zero_gas: 4,
             tx_data_non_zero_gas: 68,
+            tx_data_gas_factor: 1,
+            tx_base_size: 0,
+            tx_data_compressed: false,
             copy_gas: 3,
             extcodesize_gas: 20,
             extcodecopy_base_gas: 20,
@@ -408,6 +486,16 @@ impl Schedule {
             eip3198: false,
             max_refund_quotient: MAX_REFUND_QUOTIENT,
             eip3541: false,
+            have_push0: false,
+            eip3860: false,
+            max_initcode_size: usize::max_value(),
+            initcode_word_gas: 0,
+            eip3651: false,
+            have_transient_storage: false,
+            have_mcopy: false,
+            eip6780: false,
+            eip7702: false,
+            eip2935: false,
         }
     }
 }
@@ -428,3 +516,14 @@ fn schedule_evm_assumptions() {
     assert_eq!(s1.quad_coeff_div, 512);
     assert_eq!(s2.quad_coeff_div, 512);
 }
+
+#[test]
+#[cfg(test)]
+fn schedule_shanghai_initcode_limit() {
+    let s = Schedule::new_shanghai();
+
+    // EIP-3860 limits init code to twice the EIP-170 code size limit
+    assert!(s.eip3860);
+    assert_eq!(s.max_initcode_size, 2 * s.create_data_limit);
+    assert_eq!(s.max_initcode_size, 49152);
+}
@@ -26,6 +26,7 @@ use alloc::sync::Arc;
 use alloc::vec::Vec;
 
 use crate::access_list::AccessList;
+use crate::delegation;
 use crate::{
     CallType, ContractCreateResult, CreateContractAddress, EnvInfo, Ext, GasLeft, MessageCallResult, Result, ReturnData, Schedule, TrapKind
 };
@@ -63,6 +64,7 @@ pub struct FakeCall {
 pub struct FakeExt {
     pub initial_store: HashMap<H256, H256>,
     pub store: HashMap<H256, H256>,
+    pub transient_store: HashMap<H256, H256>,
     pub suicides: HashSet<Address>,
     pub calls: HashSet<FakeCall>,
     pub sstore_clears: i128,
@@ -136,6 +138,20 @@ impl FakeExt {
         ext
     }
 
+    /// New fake externalities with Shanghai schedule rules
+    pub fn new_shanghai(from: Address, to: Address, builtins: &[Address]) -> Self {
+        let mut ext = FakeExt::new_berlin(from, to, builtins);
+        ext.schedule = Schedule::new_shanghai();
+        ext
+    }
+
+    /// New fake externalities with Cancun schedule rules
+    pub fn new_cancun(from: Address, to: Address, builtins: &[Address]) -> Self {
+        let mut ext = FakeExt::new_berlin(from, to, builtins);
+        ext.schedule = Schedule::new_cancun();
+        ext
+    }
+
     /// Set chain ID
     pub fn with_chain_id(mut self, chain_id: u64) -> Self {
         self.chain_id = chain_id;
@@ -175,6 +191,15 @@ impl Ext for FakeExt {
         Ok(())
     }
 
+    fn transient_storage_at(&self, key: &H256) -> Result<H256> {
+        Ok(self.transient_store.get(key).cloned().unwrap_or_default())
+    }
+
+    fn set_transient_storage(&mut self, key: H256, value: H256) -> Result<()> {
+        self.transient_store.insert(key, value);
+        Ok(())
+    }
+
     fn exists(&self, address: &Address) -> Result<bool> {
         Ok(self.balances.contains_key(address))
     }
@@ -248,6 +273,10 @@ impl Ext for FakeExt {
         Ok(self.codes.get(address).map(|c| keccak(c.as_ref())))
     }
 
+    fn delegation(&self, address: &Address) -> Result<Option<Address>> {
+        Ok(self.codes.get(address).and_then(|c| delegation::target(c)))
+    }
+
     fn log(&mut self, topics: Vec<H256>, data: &[u8]) -> Result<()> {
         self.logs.push(FakeLogEntry { topics, data: data.to_vec() });
         Ok(())
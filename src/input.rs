@@ -1,34 +1,124 @@
+use crate::protocol::{RuleSet, INITIAL_PROTOCOL_VERSION};
 use crate::HashDBOracle;
 use alloc::vec::Vec;
-use brotli::decompress;
 use byteorder::{BigEndian, ByteOrder};
-use common_types::header::Header;
+use common_types::header::{Header, L1Origin};
 use common_types::l2_cfg::{L1_CROSS_LAYER_WITNESS, L2_BLOCK_MAX_GAS_LIMIT, MAX_SENDER_NONCE};
-use common_types::transaction::TypedTxId::Legacy;
+use common_types::transaction::TypedTxId::{EIP7702Transaction, Legacy};
 use common_types::transaction::{TypedTransaction, UnverifiedTransaction};
+use crypto::hash::keccak;
+use crypto::publickey::{recover, Signature};
 use ethcore::client::LastHashes;
-use ethereum_types::H256;
-use rlp::{DecoderError, Rlp};
+use ethcore::miner::{ExcludedTransaction, ExclusionReason, TransitionLimits};
+use ethereum_types::{Address, H256, U256};
+use rlp::Rlp;
 
-// format: queueNum(uint64) + queueStart(uint64) + batchNum(uint64) + batch0Time(uint64) +
+mod legacy;
+
+/// Format of the rollup input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    /// format of protocol version 0, see `legacy`.
+    Legacy,
+    /// input bounded by `TransitionLimits`, with malformed parts treated as empty and batches
+    /// optionally signed by a sequencer.
+    Bounded,
+}
+
+/// Flag of the batches version byte, set when the batches data is signed by a sequencer.
+const SIGNED_BATCHES_FLAG: u8 = 0x80;
+
+/// Preimage reader of the rollup input, accounting the bytes read against
+/// `TransitionLimits::max_preimage_bytes`.
+pub struct InputReader<'a> {
+    db: &'a HashDBOracle,
+    limits: &'a TransitionLimits,
+    /// sequencers authorized to sign batches, empty if batches need no signature.
+    sequencers: Vec<Address>,
+    preimage_bytes: usize,
+    decompressed_bytes: usize,
+}
+
+impl<'a> InputReader<'a> {
+    pub fn new(
+        db: &'a HashDBOracle, limits: &'a TransitionLimits, sequencers: Vec<Address>,
+    ) -> Self {
+        InputReader { db, limits, sequencers, preimage_bytes: 0, decompressed_bytes: 0 }
+    }
+
+    /// returns None once the preimage budget is exhausted. The preimage exhausting it is not
+    /// copied if the backend knows its length.
+    fn get(&mut self, hash: &H256, msg: &str) -> Option<Vec<u8>> {
+        if let Some(len) = self.db.value_len(hash) {
+            self.consume(len)?;
+            return Some(self.db.get(hash).expect(msg).into_vec());
+        }
+        let raw = self.db.get(hash).expect(msg).into_vec();
+        self.consume(raw.len())?;
+        Some(raw)
+    }
+
+    fn consume(&mut self, len: usize) -> Option<()> {
+        self.preimage_bytes = self.preimage_bytes.saturating_add(len);
+        if self.preimage_bytes > self.limits.max_preimage_bytes {
+            return None;
+        }
+        Some(())
+    }
+}
+
+// input format: batchHash(bytes32) + queueHash(bytes32) + [l1BlockNumber(uint64) +
+// l1BaseFee(uint256)]
+// batch format: queueNum(uint64) + queueStart(uint64) + batchNum(uint64) + batch0Time(uint64) +
 // batchLeftTimeDiff([]uint32) + batchesData
-fn load_batches_from_hashdb(db: &HashDBOracle, batch_input_hash: H256) -> Vec<Batch> {
-    let raw_input = db.get(&batch_input_hash).expect("input not found");
-    let raw_input = raw_input.into_vec();
+// An input exceeding the preimage budget or the queue cap, or too short to hold its own headers
+// yields no batches. Batch data that is too short to hold the timestamps is ignored, only the queue is
+// applied.
+fn load_batches_from_hashdb(reader: &mut InputReader, batch_input_hash: H256) -> Vec<Batch> {
+    load_batches(reader, batch_input_hash).unwrap_or_default()
+}
+
+fn load_batches(reader: &mut InputReader, batch_input_hash: H256) -> Option<Vec<Batch>> {
+    let raw_input = reader.get(&batch_input_hash, "input not found")?;
+    if raw_input.len() < 64 {
+        return None;
+    }
     let batch_hash = H256::from_slice(&raw_input[..32]);
     let queue_hash = H256::from_slice(&raw_input[32..64]);
-    let raw_batch = db.get(&batch_hash).expect("input batch not found");
-    let raw_batch = raw_batch.into_vec();
+    // l1BlockNumber(uint64) + l1BaseFee(uint256) follow the hashes, zero if absent
+    let (l1_block_number, l1_base_fee) = match raw_input.get(64..104) {
+        Some(l1_block) => {
+            (BigEndian::read_u64(&l1_block[..8]), U256::from_big_endian(&l1_block[8..]))
+        }
+        None => (0, U256::zero()),
+    };
+    let raw_batch = reader.get(&batch_hash, "input batch not found")?;
+    if raw_batch.len() < 24 {
+        return None;
+    }
     let queue_num = BigEndian::read_u64(&raw_batch[..8]) as usize;
-    let batch_num = BigEndian::read_u64(&raw_batch[16..24]) as usize;
+    if queue_num > reader.limits.max_queue_entries {
+        return None;
+    }
+    let queue_start = BigEndian::read_u64(&raw_batch[8..16]);
+    let mut batch_num = BigEndian::read_u64(&raw_batch[16..24]) as usize;
+    if batch_num > reader.limits.max_batches || raw_batch.len() < 24 + batch_num * 4 + 4 {
+        // too many batches or truncated timestamps, only apply the queue
+        batch_num = 0;
+    }
     let mut batches = Vec::with_capacity(queue_num + batch_num);
     let mut timestamps = Vec::with_capacity(batch_num);
-    let queue_txes = load_queue_txes(db, queue_hash);
-    batches.extend(
-        queue_txes
-            .iter()
-            .map(|item| Batch { transactions: item.txs.clone(), timestamp: item.timestamp }),
-    );
+    let queue_txes = load_queue_txes(reader, queue_hash, queue_start)?;
+    batches.extend(queue_txes.into_iter().map(|item| Batch {
+        transactions: item.txs,
+        timestamp: item.timestamp,
+        excluded: Vec::new(),
+        origin: L1Origin {
+            queue_start: item.queue_start,
+            queue_end: item.queue_end,
+            ..Default::default()
+        },
+    }));
 
     if batch_num > 0 {
         let timeend = 24 + batch_num * 4 + 4;
@@ -37,153 +127,597 @@ fn load_batches_from_hashdb(db: &HashDBOracle, batch_input_hash: H256) -> Vec<Ba
         let mut time = BigEndian::read_u64(&time_slice[..8]);
         timestamps.push(time);
         for i in 1..batch_num {
-            time += BigEndian::read_u32(&time_slice[4 + i * 4..]) as u64;
+            time = time.saturating_add(BigEndian::read_u32(&time_slice[4 + i * 4..]) as u64);
             timestamps.push(time);
         }
-        batches.extend(decode_batches(batches_slice, timestamps));
+        if let Some((version, data)) = open_batches(reader, &raw_batch[..timeend], batches_slice) {
+            batches.extend(decode_batches(reader, version, data, timestamps));
+        }
     }
     batches.sort_by_key(|v| v.timestamp);
 
-    batches
+    // sequencer batches consume no queue entry, they record the queue position they follow
+    let mut queue_index = queue_start;
+    for (index, batch) in batches.iter_mut().enumerate() {
+        let origin = &mut batch.origin;
+        origin.batch_index = index as u64;
+        origin.batch_input_hash = batch_input_hash;
+        origin.l1_timestamp = batch.timestamp;
+        origin.l1_block_number = l1_block_number;
+        origin.l1_base_fee = l1_base_fee;
+        if origin.queue_end > origin.queue_start {
+            queue_index = origin.queue_end;
+        } else {
+            origin.queue_start = queue_index;
+            origin.queue_end = queue_index;
+        }
+    }
+
+    Some(batches)
 }
 
-// verison(byte) + data
-// v0: 0 + rlplist(rlplist(tx))
-fn decode_batches(data: &[u8], timestamp: Vec<u64>) -> Vec<Batch> {
-    let version = data[0];
-    if version > 1 {
-        // invalid version, now only support 0, 1
-        return Vec::new();
+// version(byte) + [signature(65 bytes)] + data
+// The signature is present when the version has `SIGNED_BATCHES_FLAG` set. It is an RSV
+// signature (V in "Electrum" notation) over `keccak(queueNum ++ queueStart ++ batchNum ++
+// batch0Time ++ batchLeftTimeDiff ++ version ++ data)`, the version without the flag, so the
+// signed batches can not be replayed against another queue range.
+// Returns the version and data of the batches, or None if the batches are empty, not signed by
+// an authorized sequencer while `sequencers` is set, or signed while it is not. Such batches are
+// dropped, only the queue is applied.
+fn open_batches<'b>(reader: &InputReader, header: &[u8], data: &'b [u8]) -> Option<(u8, &'b [u8])> {
+    let (&version, data) = data.split_first()?;
+    if version & SIGNED_BATCHES_FLAG == 0 {
+        return if reader.sequencers.is_empty() { Some((version, data)) } else { None };
     }
-    let mut rlp = Rlp::new(&data[1..]);
-    let mut d: Vec<u8>; //hold the var, avoid  drop
-    match version {
-        ///just as normal, do nothing
-        0 => {}
-        /// brotli coded, try decode to rlp code
+    // without sequencer a flagged version is an invalid version
+    if reader.sequencers.is_empty() || data.len() < 65 {
+        return None;
+    }
+    let version = version & !SIGNED_BATCHES_FLAG;
+    let (signature, data) = data.split_at(65);
+    let mut message = Vec::with_capacity(header.len() + 1 + data.len());
+    message.extend_from_slice(header);
+    message.push(version);
+    message.extend_from_slice(data);
+    let signer = recover(&Signature::from_electrum(signature), &keccak(&message))?;
+    if !reader.sequencers.contains(&signer) {
+        return None;
+    }
+    Some((version, data))
+}
+
+// v0: rlplist(rlplist(tx))
+// v1: brotli(rlplist(rlplist(tx)))
+fn decode_batches(
+    reader: &mut InputReader, version: u8, data: &[u8], timestamp: Vec<u64>,
+) -> Vec<Batch> {
+    let decompressed: Vec<u8>;
+    let rlp = match version {
+        0 => Rlp::new(data),
+        // brotli coded, decode to rlp within the decompression budget
         1 => {
-            let ret = brotli::decompress(&data[1..], 4 * 1024 * 1024); // Now limit is 4MB
-            if ret.is_err() {
-                return Vec::new();
-            }
-            d = ret.unwrap();
-            rlp = Rlp::new(d.as_slice());
-        }
-        /// invalid version
-        _ => {
-            return Vec::new();
+            let budget = reader.limits.max_decompressed_bytes - reader.decompressed_bytes;
+            let limit = core::cmp::min(4 * 1024 * 1024, budget); // Now limit is 4MB
+            decompressed = match brotli::decompress(data, limit) {
+                Err(_) => return Vec::new(),
+                Ok(d) => d,
+            };
+            reader.decompressed_bytes += decompressed.len();
+            Rlp::new(decompressed.as_slice())
         }
-    }
+        // invalid version, now only support 0, 1
+        _ => return Vec::new(),
+    };
     if !rlp.is_list() {
         return Vec::new();
     }
-    let num_batches = rlp.item_count().expect("expect batch list");
+    let num_batches = match rlp.item_count() {
+        Err(_) => return Vec::new(),
+        Ok(n) => n,
+    };
     if num_batches != timestamp.len() {
         return Vec::new();
     }
     let mut batches = Vec::with_capacity(num_batches);
     for (batch, time) in rlp.iter().zip(timestamp) {
+        if batch.item_count().unwrap_or_default() > reader.limits.max_batch_transactions {
+            // oversized batch, drop it and keep the others
+            continue;
+        }
         let txs = match TypedTransaction::decode_rlp_list(&batch) {
-            Err(e) => return Vec::new(),
+            Err(_) => return Vec::new(),
             Ok(t) => t,
         };
-        let mut batch = Batch { timestamp: time, transactions: txs };
+        let mut excluded = Vec::new();
         // ensure there are not enqueued tx in batch
-        batch.transactions.retain(|tx| {
-            let sender = tx.recover_sender().unwrap_or(L1_CROSS_LAYER_WITNESS);
-            if sender == L1_CROSS_LAYER_WITNESS {
-                return false;
-            }
-            let nonce = tx.tx().nonce.as_u64();
-            if nonce >= MAX_SENDER_NONCE {
-                return false;
-            }
-            if tx.tx_type() != Legacy {
-                return false;
-            }
-            return true;
-        });
+        let transactions = txs
+            .into_iter()
+            .filter(|tx| match batch_tx_exclusion(tx) {
+                None => true,
+                Some(reason) => {
+                    excluded.push(ExcludedTransaction::new(tx.hash(), reason));
+                    false
+                }
+            })
+            .collect();
+        let batch = Batch { timestamp: time, transactions, excluded, origin: L1Origin::default() };
         batches.push(batch);
     }
 
     batches
 }
 
+fn batch_tx_exclusion(tx: &UnverifiedTransaction) -> Option<ExclusionReason> {
+    let sender = match tx.recover_sender() {
+        None => return Some(ExclusionReason::Unrecoverable),
+        Some(sender) => sender,
+    };
+    if sender == L1_CROSS_LAYER_WITNESS {
+        return Some(ExclusionReason::ReservedSender);
+    }
+    if tx.tx().nonce >= MAX_SENDER_NONCE.into() {
+        return Some(ExclusionReason::ReservedNonce);
+    }
+    if tx.tx_type() != Legacy && tx.tx_type() != EIP7702Transaction {
+        return Some(ExclusionReason::UnsupportedType);
+    }
+    None
+}
+
 pub struct Batch {
     pub timestamp: u64,
     pub transactions: Vec<UnverifiedTransaction>,
+    /// transactions dropped while decoding the batch
+    pub excluded: Vec<ExcludedTransaction>,
+    /// L1 data the batch was derived from
+    pub origin: L1Origin,
 }
 
 pub struct QueueTxInfo {
     txs: Vec<UnverifiedTransaction>,
     timestamp: u64,
+    queue_start: u64,
+    queue_end: u64,
 }
 
-fn load_queue_txes(db: &HashDBOracle, hash: H256) -> Vec<QueueTxInfo> {
-    let raw = db.get(&hash).expect("queue preimage not found");
-    let raw = raw.into_vec();
+fn load_queue_txes(
+    reader: &mut InputReader, hash: H256, queue_start: u64,
+) -> Option<Vec<QueueTxInfo>> {
+    let raw = reader.get(&hash, "queue preimage not found")?;
+    if raw.len() / 40 > reader.limits.max_queue_entries {
+        return None;
+    }
     let mut result: Vec<QueueTxInfo> = Vec::new();
-    raw.chunks_exact(40).for_each(|chunk| {
+    for (i, chunk) in raw.chunks_exact(40).enumerate() {
+        let queue_index = queue_start.saturating_add(i as u64);
         let txhash = H256::from_slice(&chunk[..32]);
         let timestamp = BigEndian::read_u64(&chunk[32..]);
-        let raw = db.get(&txhash).expect("queue tx not found");
+        let raw = reader.get(&txhash, "queue tx not found")?;
         let rlp = Rlp::new(&raw);
-        let tx = TypedTransaction::decode_rlp(&rlp).unwrap();
+        // an undecodable queue entry is skipped
+        let tx = match TypedTransaction::decode_rlp(&rlp) {
+            Err(_) => continue,
+            Ok(tx) => tx,
+        };
         let q_info = result.iter_mut().rfind(|info| info.timestamp == timestamp);
         match q_info {
             None => {
                 let mut txs = Vec::new();
                 txs.push(tx);
-                result.push(QueueTxInfo { timestamp, txs })
+                result.push(QueueTxInfo {
+                    timestamp,
+                    txs,
+                    queue_start: queue_index,
+                    queue_end: queue_index.saturating_add(1),
+                })
             }
             Some(q) => {
-                let total_gas = q.txs.iter().fold(0, |gas, tx| gas + tx.tx().gas.as_u32());
-                if total_gas > L2_BLOCK_MAX_GAS_LIMIT {
+                let total_gas =
+                    q.txs.iter().fold(U256::zero(), |gas, tx| gas.saturating_add(tx.tx().gas));
+                if total_gas > L2_BLOCK_MAX_GAS_LIMIT.into() {
                     let mut txs = Vec::new();
                     txs.push(tx);
-                    result.push(QueueTxInfo { timestamp, txs });
+                    result.push(QueueTxInfo {
+                        timestamp,
+                        txs,
+                        queue_start: queue_index,
+                        queue_end: queue_index.saturating_add(1),
+                    });
                 } else {
                     q.txs.push(tx);
+                    q.queue_end = queue_index.saturating_add(1);
                 }
             }
         }
-    });
+    }
     result.sort_by_key(|i| i.timestamp);
-    return result;
+    Some(result)
+}
+
+// format: batchInputHash(bytes32) + prevBlockHash(bytes32) + protocolVersion(uint8)
+// entries without the version byte use `INITIAL_PROTOCOL_VERSION`.
+pub struct RollupEntry {
+    pub batch_input_hash: H256,
+    pub prev_block_hash: H256,
+    pub version: u8,
+}
+
+impl RollupEntry {
+    pub fn load_from_hashdb(db: &HashDBOracle, entry_hash: H256) -> RollupEntry {
+        let raw = db.get(&entry_hash).expect("input not found");
+        let batch_input_hash = H256::from_slice(&raw[..32]);
+        let prev_block_hash = H256::from_slice(&raw[32..64]);
+        let version = raw.get(64).copied().unwrap_or(INITIAL_PROTOCOL_VERSION);
+        RollupEntry { batch_input_hash, prev_block_hash, version }
+    }
+
+    /// Header of the block the entry builds on, read without any input budget so entries which
+    /// are rejected still report it.
+    pub fn load_prev_header(&self, db: &HashDBOracle) -> Header {
+        let raw = db.get(&self.prev_block_hash).expect("previous header not found");
+        Header::decode_rlp(&Rlp::new(&raw), u64::MAX).expect("load header err")
+    }
 }
 
 pub struct RollupInput {
     pub prev_header: Header,
+    /// hashes of the previous block and its ancestors, most recent first.
+    pub last_hashes: LastHashes,
     pub batches: Vec<Batch>,
 }
 
 impl RollupInput {
-    pub fn load_from_hashdb(db: &HashDBOracle, entry_hash: H256) -> RollupInput {
-        let raw = db.get(&entry_hash).expect("input not found");
-        let batch_input_hash = H256::from_slice(&raw[..32]);
-        let batches = load_batches_from_hashdb(db, batch_input_hash);
-        let prev_block_hash = H256::from_slice(&raw[32..64]);
-        let header = load_header(db, prev_block_hash);
-        RollupInput { prev_header: header, batches }
+    /// Load the input of `entry` in the format of `rules`, with as many ancestor hashes of the
+    /// previous block as `ancestry` asks for, and batches signed by one of the `sequencers` of
+    /// the previous block. An input reading more than `max_preimage_bytes`, ancestors included,
+    /// yields no batches.
+    pub fn load_from_hashdb(
+        db: &HashDBOracle, entry: &RollupEntry, rules: &RuleSet,
+        ancestry: impl FnOnce(&Header) -> u64, sequencers: impl FnOnce(&Header) -> Vec<Address>,
+    ) -> RollupInput {
+        let mut reader = InputReader::new(db, &rules.limits, Vec::new());
+        let header =
+            load_header(&mut reader, entry.prev_block_hash).expect("previous header over budget");
+        reader.sequencers = sequencers(&header);
+        let depth = ancestry(&header);
+        match load_last_hashes(&mut reader, header.hash(), header.number().min(depth)) {
+            Some(last_hashes) => {
+                let batches = match rules.input {
+                    InputFormat::Legacy => {
+                        legacy::load_batches_from_hashdb(db, entry.batch_input_hash)
+                    }
+                    InputFormat::Bounded => {
+                        load_batches_from_hashdb(&mut reader, entry.batch_input_hash)
+                    }
+                };
+                RollupInput { last_hashes, batches, prev_header: header }
+            }
+            None => RollupInput {
+                last_hashes: alloc::vec![header.hash()],
+                batches: Vec::new(),
+                prev_header: header,
+            },
+        }
     }
 }
 
-pub fn load_header(db: &HashDBOracle, hash: H256) -> Header {
-    let raw = db.get(&hash).expect("input not found");
+pub fn load_header(reader: &mut InputReader, hash: H256) -> Option<Header> {
+    let raw = reader.get(&hash, "input not found")?;
     // TODO: eip1559 base fee
-    Header::decode_rlp(&Rlp::new(&raw), u64::MAX).expect("load header err")
+    Some(Header::decode_rlp(&Rlp::new(&raw), u64::MAX).expect("load header err"))
 }
 
-pub fn load_last_hashes(db: &HashDBOracle, mut hash: H256, height: u64) -> LastHashes {
+/// Hashes of the block `hash` and of its `count` ancestors, at most 255.
+pub fn load_last_hashes(
+    reader: &mut InputReader, mut hash: H256, count: u64,
+) -> Option<LastHashes> {
     let mut hashes = Vec::with_capacity(256);
     hashes.push(hash);
 
-    let count = if height < 255 { height } else { 255 };
-    for _ in 0..count {
-        let header = load_header(db, hash);
+    for _ in 0..count.min(255) {
+        let header = load_header(reader, hash)?;
         hash = *header.parent_hash();
         hashes.push(hash);
     }
 
-    hashes
+    Some(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{rule_set, PROTOCOL_VERSION_1};
+    use common_types::l2_cfg::L2_CHAIN_ID;
+    use common_types::transaction::{Action, Transaction};
+    use crypto::publickey::{public_to_address, sign, Generator, Random, Secret};
+    use ethcore::state::backend::ProofCheck;
+    use hash_db::HashDB;
+    use rlp::RlpStream;
+
+    fn tx(nonce: u64) -> UnverifiedTransaction {
+        TypedTransaction::Legacy(Transaction {
+            nonce: nonce.into(),
+            gas: 21_000.into(),
+            action: Action::Call(Address::zero()),
+            ..Default::default()
+        })
+        .null_sign(L2_CHAIN_ID)
+        .into()
+    }
+
+    fn batches_rlp(batches: &[Vec<UnverifiedTransaction>]) -> Vec<u8> {
+        let mut s = RlpStream::new_list(batches.len());
+        for txs in batches {
+            UnverifiedTransaction::rlp_append_list(&mut s, txs);
+        }
+        s.out().to_vec()
+    }
+
+    // single uncompressed meta-block followed by an empty last one
+    fn brotli_uncompressed(data: &[u8]) -> Vec<u8> {
+        let header = ((data.len() as u32 - 1) << 4) | (1 << 20);
+        let mut out = header.to_le_bytes()[..3].to_vec();
+        out.extend_from_slice(data);
+        out.push(0x03);
+        out
+    }
+
+    // queueNum + queueStart + batchNum + batch0Time + batchLeftTimeDiff, the part of the input
+    // covered by signatures
+    fn batches_header(queue_num: usize, timestamps: &[u64]) -> Vec<u8> {
+        let mut header = (queue_num as u64).to_be_bytes().to_vec();
+        header.extend_from_slice(&0u64.to_be_bytes());
+        header.extend_from_slice(&(timestamps.len() as u64).to_be_bytes());
+        if let Some(first) = timestamps.first() {
+            header.extend_from_slice(&first.to_be_bytes());
+            for pair in timestamps.windows(2) {
+                header.extend_from_slice(&((pair[1] - pair[0]) as u32).to_be_bytes());
+            }
+        }
+        header
+    }
+
+    // queue entries are (tx, timestamp), batches are the versioned batches data
+    fn insert_input(
+        db: &mut ProofCheck, queue: &[(UnverifiedTransaction, u64)], timestamps: &[u64],
+        batches: &[u8],
+    ) -> H256 {
+        let mut raw_queue = Vec::new();
+        for (tx, timestamp) in queue {
+            raw_queue.extend_from_slice(db.insert(&tx.encode()).as_bytes());
+            raw_queue.extend_from_slice(&timestamp.to_be_bytes());
+        }
+        let queue_hash = db.insert(&raw_queue);
+        let mut raw_batch = batches_header(queue.len(), timestamps);
+        raw_batch.extend_from_slice(batches);
+        let batch_hash = db.insert(&raw_batch);
+        db.insert(&[batch_hash.as_bytes(), queue_hash.as_bytes()].concat())
+    }
+
+    fn load(
+        db: &ProofCheck, hash: H256, limits: &TransitionLimits, sequencers: &[Address],
+    ) -> Vec<Batch> {
+        load_batches_from_hashdb(&mut InputReader::new(db, limits, sequencers.to_vec()), hash)
+    }
+
+    // batches data signed with `secret` over the batches header, version and data
+    fn signed(
+        secret: &Secret, queue_num: usize, timestamps: &[u64], version: u8, data: Vec<u8>,
+    ) -> Vec<u8> {
+        let mut message = batches_header(queue_num, timestamps);
+        message.push(version);
+        message.extend_from_slice(&data);
+        let signature = sign(secret, &keccak(&message)).unwrap();
+        let mut out = alloc::vec![version | SIGNED_BATCHES_FLAG];
+        out.extend_from_slice(&signature.into_electrum());
+        out.extend(data);
+        out
+    }
+
+    fn versioned(version: u8, data: Vec<u8>) -> Vec<u8> {
+        let mut out = alloc::vec![version];
+        out.extend(data);
+        out
+    }
+
+    #[test]
+    fn preimage_budget_rejects_input() {
+        let mut db = ProofCheck::new(&[]);
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[], &[]);
+        assert_eq!(load(&db, hash, &TransitionLimits::default(), &[]).len(), 1);
+
+        let limits = TransitionLimits { max_preimage_bytes: 64 + 24, ..Default::default() };
+        assert!(load(&db, hash, &limits, &[]).is_empty());
+    }
+
+    #[test]
+    fn preimage_budget_counts_ancestors() {
+        let mut db = ProofCheck::new(&[]);
+        let mut header = Header::default();
+        header.set_number(1);
+        header.set_parent_hash(db.insert(&rlp::encode(&Header::default())));
+        let header_rlp = rlp::encode(&header);
+        let prev_block_hash = db.insert(&header_rlp);
+        let queue_tx = tx(0);
+        let batch_input_hash = insert_input(&mut db, &[(queue_tx.clone(), 1)], &[], &[]);
+        let entry = RollupEntry { batch_input_hash, prev_block_hash, version: 0 };
+        let limits = TransitionLimits {
+            max_preimage_bytes: header_rlp.len() + 64 + 24 + 40 + queue_tx.encode().len(),
+            ..Default::default()
+        };
+        let rules = RuleSet { limits, ..rule_set(PROTOCOL_VERSION_1).unwrap() };
+
+        let input = RollupInput::load_from_hashdb(&db, &entry, &rules, |_| 0, |_| Vec::new());
+        assert_eq!(input.last_hashes, alloc::vec![prev_block_hash]);
+        assert_eq!(input.batches.len(), 1);
+
+        let input = RollupInput::load_from_hashdb(&db, &entry, &rules, |_| 255, |_| Vec::new());
+        assert!(input.batches.is_empty());
+    }
+
+    #[test]
+    fn queue_over_max_entries_rejects_input() {
+        let mut db = ProofCheck::new(&[]);
+        let queue = [(tx(0), 1), (tx(1), 2), (tx(2), 3)];
+        let data = versioned(0, batches_rlp(&[alloc::vec![tx(3)]]));
+        let hash = insert_input(&mut db, &queue, &[4], &data);
+
+        let limits = TransitionLimits { max_queue_entries: 3, ..Default::default() };
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches.len(), 4);
+        assert_eq!(batches[2].origin.queue_end, 3);
+
+        // no queue entry is dropped, the input is rejected as a whole
+        let limits = TransitionLimits { max_queue_entries: 2, ..Default::default() };
+        assert!(load(&db, hash, &limits, &[]).is_empty());
+    }
+
+    #[test]
+    fn too_many_batches_applies_queue() {
+        let mut db = ProofCheck::new(&[]);
+        let data = versioned(0, batches_rlp(&[alloc::vec![tx(0)], alloc::vec![tx(1)]]));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2, 3], &data);
+        assert_eq!(load(&db, hash, &TransitionLimits::default(), &[]).len(), 3);
+
+        let limits = TransitionLimits { max_batches: 1, ..Default::default() };
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].origin.queue_end, 1);
+    }
+
+    #[test]
+    fn oversized_batch_applies_queue() {
+        let mut db = ProofCheck::new(&[]);
+        let data = versioned(0, batches_rlp(&[alloc::vec![tx(0), tx(1)]]));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+        let limits = TransitionLimits { max_batch_transactions: 2, ..Default::default() };
+        assert_eq!(load(&db, hash, &limits, &[]).len(), 2);
+
+        let limits = TransitionLimits { max_batch_transactions: 1, ..Default::default() };
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].origin.queue_end, 1);
+    }
+
+    #[test]
+    fn oversized_batch_keeps_other_batches() {
+        let mut db = ProofCheck::new(&[]);
+        let data = versioned(0, batches_rlp(&[alloc::vec![tx(0), tx(1)], alloc::vec![tx(2)]]));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2, 3], &data);
+        let limits = TransitionLimits { max_batch_transactions: 1, ..Default::default() };
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].timestamp, 3);
+    }
+
+    #[test]
+    fn decompression_budget_applies_queue() {
+        let mut db = ProofCheck::new(&[]);
+        let raw = batches_rlp(&[alloc::vec![tx(0)]]);
+        let data = versioned(1, brotli_uncompressed(&raw));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+        let limits = TransitionLimits { max_decompressed_bytes: raw.len(), ..Default::default() };
+        assert_eq!(load(&db, hash, &limits, &[]).len(), 2);
+
+        let limits =
+            TransitionLimits { max_decompressed_bytes: raw.len() - 1, ..Default::default() };
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].origin.queue_end, 1);
+    }
+
+    #[test]
+    fn batches_signed_by_sequencer() {
+        let mut db = ProofCheck::new(&[]);
+        let sequencer = Random.generate();
+        let sequencers = [public_to_address(sequencer.public())];
+        let data = signed(sequencer.secret(), 1, &[2], 0, batches_rlp(&[alloc::vec![tx(0)]]));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+        let limits = TransitionLimits::default();
+
+        let batches = load(&db, hash, &limits, &sequencers);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].timestamp, 2);
+    }
+
+    #[test]
+    fn batches_signed_for_other_queue_apply_queue() {
+        let mut db = ProofCheck::new(&[]);
+        let sequencer = Random.generate();
+        let sequencers = [public_to_address(sequencer.public())];
+        // signed with an empty queue, replayed after one queue entry
+        let data = signed(sequencer.secret(), 0, &[2], 0, batches_rlp(&[alloc::vec![tx(0)]]));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+
+        let batches = load(&db, hash, &TransitionLimits::default(), &sequencers);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].origin.queue_end, 1);
+    }
+
+    #[test]
+    fn batches_signed_by_other_key_apply_queue() {
+        let mut db = ProofCheck::new(&[]);
+        let sequencers = [public_to_address(Random.generate().public())];
+        let raw = batches_rlp(&[alloc::vec![tx(0)]]);
+        let data = signed(Random.generate().secret(), 1, &[2], 0, raw);
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+
+        let batches = load(&db, hash, &TransitionLimits::default(), &sequencers);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].origin.queue_end, 1);
+    }
+
+    #[test]
+    fn batches_with_short_signature_apply_queue() {
+        let mut db = ProofCheck::new(&[]);
+        let sequencer = Random.generate();
+        let sequencers = [public_to_address(sequencer.public())];
+        let mut data = signed(sequencer.secret(), 1, &[2], 0, Vec::new());
+        data.truncate(1 + 64);
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+
+        let batches = load(&db, hash, &TransitionLimits::default(), &sequencers);
+        assert_eq!(batches.len(), 1);
+    }
+
+    #[test]
+    fn unsigned_batches_need_no_sequencer() {
+        let mut db = ProofCheck::new(&[]);
+        let raw = batches_rlp(&[alloc::vec![tx(0)]]);
+        let unsigned = insert_input(&mut db, &[(tx(0), 1)], &[2], &versioned(0, raw.clone()));
+        let sequencer = Random.generate();
+        let data = signed(sequencer.secret(), 1, &[2], 0, raw);
+        let flagged = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+        let limits = TransitionLimits::default();
+
+        assert_eq!(load(&db, unsigned, &limits, &[]).len(), 2);
+        // a flagged version is invalid without sequencer
+        assert_eq!(load(&db, flagged, &limits, &[]).len(), 1);
+        // unsigned batches are dropped once a sequencer is set
+        let sequencers = [public_to_address(sequencer.public())];
+        assert_eq!(load(&db, unsigned, &limits, &sequencers).len(), 1);
+    }
+
+    #[test]
+    fn l1_block_attributes_from_input() {
+        let mut db = ProofCheck::new(&[]);
+        let data = versioned(0, batches_rlp(&[alloc::vec![tx(1)]]));
+        let hash = insert_input(&mut db, &[(tx(0), 1)], &[2], &data);
+        let limits = TransitionLimits::default();
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches[1].origin.l1_block_number, 0);
+        assert_eq!(batches[1].origin.l1_base_fee, U256::zero());
+
+        let mut raw_input = db.get(&hash).unwrap().into_vec();
+        raw_input.extend_from_slice(&18_500_000u64.to_be_bytes());
+        raw_input.extend_from_slice(H256::from_low_u64_be(30_000_000_000).as_bytes());
+        let hash = db.insert(&raw_input);
+        let batches = load(&db, hash, &limits, &[]);
+        assert_eq!(batches.len(), 2);
+        for batch in batches {
+            assert_eq!(batch.origin.batch_input_hash, hash);
+            assert_eq!(batch.origin.l1_block_number, 18_500_000);
+            assert_eq!(batch.origin.l1_base_fee, U256::from(30_000_000_000u64));
+        }
+    }
 }
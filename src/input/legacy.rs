@@ -0,0 +1,163 @@
+//! Rollup input format of protocol version 0.
+//!
+//! Kept as it was when version 0 was live so its history stays provable: the input is not
+//! bounded by `TransitionLimits`, batches are not signed and malformed input panics the
+//! transition.
+
+use alloc::vec::Vec;
+use byteorder::{BigEndian, ByteOrder};
+use common_types::header::L1Origin;
+use common_types::l2_cfg::{L1_CROSS_LAYER_WITNESS, L2_BLOCK_MAX_GAS_LIMIT, MAX_SENDER_NONCE};
+use common_types::transaction::TypedTxId::Legacy;
+use common_types::transaction::{TypedTransaction, UnverifiedTransaction};
+use ethcore::miner::{ExcludedTransaction, ExclusionReason};
+use ethereum_types::H256;
+use rlp::Rlp;
+
+use super::Batch;
+use crate::HashDBOracle;
+
+// format: queueNum(uint64) + queueStart(uint64) + batchNum(uint64) + batch0Time(uint64) +
+// batchLeftTimeDiff([]uint32) + batchesData
+pub fn load_batches_from_hashdb(db: &HashDBOracle, batch_input_hash: H256) -> Vec<Batch> {
+    let raw_input = db.get(&batch_input_hash).expect("input not found");
+    let raw_input = raw_input.into_vec();
+    let batch_hash = H256::from_slice(&raw_input[..32]);
+    let queue_hash = H256::from_slice(&raw_input[32..64]);
+    let raw_batch = db.get(&batch_hash).expect("input batch not found");
+    let raw_batch = raw_batch.into_vec();
+    let queue_num = BigEndian::read_u64(&raw_batch[..8]) as usize;
+    let batch_num = BigEndian::read_u64(&raw_batch[16..24]) as usize;
+    let mut batches = Vec::with_capacity(queue_num + batch_num);
+    let mut timestamps = Vec::with_capacity(batch_num);
+    let queue_txes = load_queue_txes(db, queue_hash);
+    batches.extend(queue_txes.into_iter().map(|item| Batch {
+        transactions: item.txs,
+        timestamp: item.timestamp,
+        excluded: Vec::new(),
+        origin: L1Origin::default(),
+    }));
+
+    if batch_num > 0 {
+        let timeend = 24 + batch_num * 4 + 4;
+        let time_slice = &raw_batch[24..timeend];
+        let batches_slice = &raw_batch[timeend..raw_batch.len()];
+        let mut time = BigEndian::read_u64(&time_slice[..8]);
+        timestamps.push(time);
+        for i in 1..batch_num {
+            time += BigEndian::read_u32(&time_slice[4 + i * 4..]) as u64;
+            timestamps.push(time);
+        }
+        batches.extend(decode_batches(batches_slice, timestamps));
+    }
+    batches.sort_by_key(|v| v.timestamp);
+
+    batches
+}
+
+// verison(byte) + data
+// v0: 0 + rlplist(rlplist(tx))
+// v1: 1 + brotli(rlplist(rlplist(tx)))
+fn decode_batches(data: &[u8], timestamp: Vec<u64>) -> Vec<Batch> {
+    let version = data[0];
+    if version > 1 {
+        // invalid version, now only support 0, 1
+        return Vec::new();
+    }
+    let mut rlp = Rlp::new(&data[1..]);
+    let d: Vec<u8>; //hold the var, avoid  drop
+    if version == 1 {
+        // brotli coded, try decode to rlp code
+        let ret = brotli::decompress(&data[1..], 4 * 1024 * 1024); // Now limit is 4MB
+        if ret.is_err() {
+            return Vec::new();
+        }
+        d = ret.unwrap();
+        rlp = Rlp::new(d.as_slice());
+    }
+    if !rlp.is_list() {
+        return Vec::new();
+    }
+    let num_batches = rlp.item_count().expect("expect batch list");
+    if num_batches != timestamp.len() {
+        return Vec::new();
+    }
+    let mut batches = Vec::with_capacity(num_batches);
+    for (batch, time) in rlp.iter().zip(timestamp) {
+        let txs = match TypedTransaction::decode_rlp_list(&batch) {
+            Err(_) => return Vec::new(),
+            Ok(t) => t,
+        };
+        let mut excluded = Vec::new();
+        // ensure there are not enqueued tx in batch
+        let transactions = txs
+            .into_iter()
+            .filter(|tx| match batch_tx_exclusion(tx) {
+                None => true,
+                Some(reason) => {
+                    excluded.push(ExcludedTransaction::new(tx.hash(), reason));
+                    false
+                }
+            })
+            .collect();
+        let batch = Batch { timestamp: time, transactions, excluded, origin: L1Origin::default() };
+        batches.push(batch);
+    }
+
+    batches
+}
+
+fn batch_tx_exclusion(tx: &UnverifiedTransaction) -> Option<ExclusionReason> {
+    let sender = match tx.recover_sender() {
+        None => return Some(ExclusionReason::Unrecoverable),
+        Some(sender) => sender,
+    };
+    if sender == L1_CROSS_LAYER_WITNESS {
+        return Some(ExclusionReason::ReservedSender);
+    }
+    if tx.tx().nonce.as_u64() >= MAX_SENDER_NONCE {
+        return Some(ExclusionReason::ReservedNonce);
+    }
+    if tx.tx_type() != Legacy {
+        return Some(ExclusionReason::UnsupportedType);
+    }
+    None
+}
+
+struct QueueTxInfo {
+    txs: Vec<UnverifiedTransaction>,
+    timestamp: u64,
+}
+
+fn load_queue_txes(db: &HashDBOracle, hash: H256) -> Vec<QueueTxInfo> {
+    let raw = db.get(&hash).expect("queue preimage not found");
+    let raw = raw.into_vec();
+    let mut result: Vec<QueueTxInfo> = Vec::new();
+    raw.chunks_exact(40).for_each(|chunk| {
+        let txhash = H256::from_slice(&chunk[..32]);
+        let timestamp = BigEndian::read_u64(&chunk[32..]);
+        let raw = db.get(&txhash).expect("queue tx not found");
+        let rlp = Rlp::new(&raw);
+        let tx = TypedTransaction::decode_rlp(&rlp).unwrap();
+        let q_info = result.iter_mut().rfind(|info| info.timestamp == timestamp);
+        match q_info {
+            None => {
+                let mut txs = Vec::new();
+                txs.push(tx);
+                result.push(QueueTxInfo { timestamp, txs })
+            }
+            Some(q) => {
+                let total_gas = q.txs.iter().fold(0, |gas, tx| gas + tx.tx().gas.as_u32());
+                if total_gas > L2_BLOCK_MAX_GAS_LIMIT {
+                    let mut txs = Vec::new();
+                    txs.push(tx);
+                    result.push(QueueTxInfo { timestamp, txs });
+                } else {
+                    q.txs.push(tx);
+                }
+            }
+        }
+    });
+    result.sort_by_key(|i| i.timestamp);
+    result
+}
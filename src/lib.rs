@@ -12,17 +12,23 @@ use common_types::header::Header;
 use common_types::l2_cfg::{
     L2_BLOCK_MAX_GAS_LIMIT, L2_BLOCK_MIN_GAS_LIMIT, L2_CHAIN_ID, L2_CROSS_LAYER_WITNESS, L2_FEE_COLLECTOR
 };
-use ethcore::engines::L2Seal;
-use ethcore::miner::{generate_block, BlockGenInfo};
+use common_types::log_entry::LogEntry;
+use ethcore::engines::{l2_messages, l2_messages_root, L2Seal};
+use ethcore::miner::{
+    generate_block, parent_sequencers, BlockGenInfo, ExcludedTransaction, ExclusionReason
+};
 use hash_db::HashDB;
-use input::RollupInput;
+use input::{RollupEntry, RollupInput};
 use keccak_hasher::KeccakHasher;
 use trie_db::DBValue;
 
-use crate::input::load_last_hashes;
+use crate::output::OutputRoot;
+use crate::protocol::RuleSet;
 
 mod input;
 mod machine;
+pub mod output;
+pub mod protocol;
 
 type HashDBOracle = dyn HashDB<KeccakHasher, DBValue>;
 
@@ -32,38 +38,145 @@ pub fn state_transition(
     state_transition_to_header(db, entry_hash).hash()
 }
 
+/// Output root hash of the transition, as posted to L1.
+pub fn state_transition_output_root(
+    db: impl HashDB<KeccakHasher, DBValue> + Clone + 'static, entry_hash: H256,
+) -> H256 {
+    execute_transition(db, entry_hash).output_root().hash()
+}
+
 pub fn state_transition_to_header(
     db: impl HashDB<KeccakHasher, DBValue> + Clone + 'static, entry_hash: H256,
 ) -> Header {
-    let input = RollupInput::load_from_hashdb(&db, entry_hash);
+    execute_transition(db, entry_hash).header
+}
+
+/// Result of a state transition.
+pub struct TransitionOutput {
+    /// header of the last produced block, or the previous header if no block was produced.
+    pub header: Header,
+    /// transactions of the input which were not included in any block, with the reason.
+    pub excluded: Vec<ExcludedTransaction>,
+    /// L2 to L1 messages sent in the produced blocks, in execution order.
+    pub messages: Vec<LogEntry>,
+}
+
+impl TransitionOutput {
+    /// Output root committing to the last block and the messages sent in the transition.
+    pub fn output_root(&self) -> OutputRoot {
+        OutputRoot {
+            state_root: *self.header.state_root(),
+            block_hash: self.header.hash(),
+            message_root: l2_messages_root(self.messages.iter()),
+        }
+    }
+}
+
+/// Execute the transition of `entry_hash`, reporting the excluded transactions along the header.
+///
+/// The transition follows the rule set of the entry protocol version. An entry with an unknown
+/// version produces no block.
+pub fn execute_transition(
+    db: impl HashDB<KeccakHasher, DBValue> + Clone + 'static, entry_hash: H256,
+) -> TransitionOutput {
+    let entry = RollupEntry::load_from_hashdb(&db, entry_hash);
+    match protocol::rule_set(entry.version) {
+        Some(rules) => execute_with_rules(db, &entry, &rules),
+        None => TransitionOutput {
+            header: entry.load_prev_header(&db),
+            excluded: Vec::new(),
+            messages: Vec::new(),
+        },
+    }
+}
+
+fn execute_with_rules(
+    db: impl HashDB<KeccakHasher, DBValue> + Clone + 'static, entry: &RollupEntry, rules: &RuleSet,
+) -> TransitionOutput {
+    let limits = &rules.limits;
+    let machine = (rules.machine)();
+    let eip2935_transition = machine.params().eip2935_transition;
+    // once BLOCKHASH reads the history contract only the parent hash is needed, until it holds a
+    // full window the ancestors are loaded as well
+    let ancestry = |prev: &Header| {
+        if prev.number().saturating_add(1) >= eip2935_transition.saturating_add(256) {
+            0
+        } else {
+            255
+        }
+    };
+    // batches are signed by the sequencers registered before the transition
+    let sequencers = |prev: &Header| match rules.sequencer_registry {
+        Some(registry) => parent_sequencers(Box::new(db.clone()), &machine, prev, &registry),
+        None => Vec::new(),
+    };
+    let input = RollupInput::load_from_hashdb(&db, entry, rules, ancestry, sequencers);
     let mut prev = input.prev_header;
+    let mut latest_hashes = input.last_hashes;
     let batches = input.batches;
 
-    let latest_hashes = load_last_hashes(&db, prev.hash(), prev.number());
-    let machine = machine::create_l2_machine();
     let mut engine = L2Seal::new(0, machine);
+    engine.set_seal_layout(rules.seal);
+    let mut excluded = Vec::new();
+    let mut messages = Vec::new();
     for mut batch in batches {
-        let db_clone = Box::new(db.clone());
         engine.set_timestamp(batch.timestamp);
+        excluded.append(&mut batch.excluded);
 
         // remove tx that's chain id is incorrectly
-        batch.transactions.retain(|t| t.chain_id.unwrap_or_default() == L2_CHAIN_ID);
-
-        let info = BlockGenInfo::new(
-            prev,
-            Arc::new(latest_hashes.clone()),
-            L2_FEE_COLLECTOR,
-            (L2_BLOCK_MIN_GAS_LIMIT.into(), L2_BLOCK_MAX_GAS_LIMIT.into()),
-            Vec::new(),
-        );
-        if let Some(block) =
-            generate_block(db_clone, &engine, &info, batch.transactions, L2_CROSS_LAYER_WITNESS)
-        {
-            prev = block.header.clone();
-        } else {
-            prev = info.parent_block_header;
+        batch.transactions.retain(|t| {
+            if t.chain_id.unwrap_or_default() == L2_CHAIN_ID {
+                return true;
+            }
+            excluded.push(ExcludedTransaction::new(t.hash(), ExclusionReason::WrongChainId));
+            false
+        });
+
+        // transactions exceeding the block gas limit spill over into follow-up blocks with the
+        // same timestamp if the rules carry them over.
+        let mut txs = batch.transactions;
+        let extra_data =
+            if rules.l1_origin { rlp::encode(&batch.origin).to_vec() } else { Vec::new() };
+        loop {
+            let db_clone = Box::new(db.clone());
+            let info = BlockGenInfo::new(
+                prev,
+                Arc::new(latest_hashes.clone()),
+                L2_FEE_COLLECTOR,
+                (L2_BLOCK_MIN_GAS_LIMIT.into(), L2_BLOCK_MAX_GAS_LIMIT.into()),
+                extra_data.clone(),
+                limits.max_block_transactions,
+                rules.carry_over,
+            );
+            // hashes of the transactions left over if no block can be generated
+            let pending: Vec<H256> = txs.iter().map(|t| t.hash()).collect();
+            match generate_block(db_clone, &engine, &info, txs, L2_CROSS_LAYER_WITNESS) {
+                Some(mut generated) => {
+                    let block = &generated.block;
+                    messages.extend(l2_messages(&block.receipts, L2_CROSS_LAYER_WITNESS).cloned());
+                    prev = block.header.clone();
+                    if rules.chain_last_hashes {
+                        latest_hashes.insert(0, prev.hash());
+                        latest_hashes.truncate(256);
+                    }
+                    txs = generated.overflow;
+                    excluded.append(&mut generated.excluded);
+                }
+                None => {
+                    prev = info.parent_block_header;
+                    excluded.extend(
+                        pending
+                            .into_iter()
+                            .map(|hash| ExcludedTransaction::new(hash, ExclusionReason::NoBlock)),
+                    );
+                    break;
+                }
+            }
+            if txs.is_empty() {
+                break;
+            }
         }
     }
 
-    prev
+    TransitionOutput { header: prev, excluded, messages }
 }
@@ -1,18 +1,56 @@
 use alloc::collections::BTreeMap;
 use alloc::string::ToString;
-use common_types::l2_cfg::{L2_BLOCK_MIN_GAS_LIMIT, L2_CHAIN_ID, L2_MAX_TRANSACTION_SIZE};
+use alloc::vec::Vec;
+use common_types::l2_cfg::{INTRINSIC_GAS_FACTOR, L2_BLOCK_MIN_GAS_LIMIT, L2_CHAIN_ID, L2_CROSS_LAYER_WITNESS, L2_DEPLOYER_ALLOWLIST, L2_ENQUEUED_GAS_LIMIT, L2_L1_ATTRIBUTES, L2_L1_FEE_ORACLE, L2_L1_FEE_VAULT, L2_MAX_TRANSACTION_SIZE, TX_BASE_SIZE};
 use common_types::BlockNumber;
-use ethcore::machine::EthereumMachine;
+use ethcore::machine::{EthereumMachine, StatePatch, HISTORY_STORAGE_ADDRESS};
 use ethcore::spec::CommonParams;
 use ethcore_builtin::{
-    AltBn128ConstOperations, AltBn128PairingPrice, AltBn128PairingPricer, Blake2F, Bn128Add, Bn128Mul, Bn128Pairing, Builtin, EcRecover, EthereumBuiltin, Identity, Linear, Modexp, ModexpPricer, Pricing, Ripemd160, Sha256
+    AltBn128ConstOperations, AltBn128PairingPrice, AltBn128PairingPricer, Blake2F, Bn128Add, Bn128Mul, Bn128Pairing, Builtin, EcRecover, EthereumBuiltin, Identity, Linear, MessagePasser, Modexp, ModexpPricer, Pricing, Ripemd160, Sha256
+};
+#[cfg(feature = "p256-verify")]
+use ethcore_builtin::P256Verify;
+#[cfg(feature = "bls12-381")]
+use ethcore_builtin::{
+    Bls12ConstOperations, Bls12G1Add, Bls12G1Mul, Bls12G1MultiExp, Bls12G2Add, Bls12G2Mul, Bls12G2MultiExp, Bls12MapFp2ToG2, Bls12MapFpToG1, Bls12MultiexpPricerG1, Bls12MultiexpPricerG2, Bls12Pairing, Bls12PairingPrice, Bls12PairingPricer
 };
 use ethereum_types::{Address, H160, U256};
 
+// EIP-2537 precompiles, activated by scheduling a network upgrade. Only registered with the
+// `bls12-381` feature, which has to be enabled for the native checker and the guest alike.
+#[cfg(feature = "bls12-381")]
+const BLS12_381_TRANSITION: BlockNumber = BlockNumber::MAX;
+// RIP-7212 secp256r1 verification, activated by scheduling a network upgrade. Only registered
+// with the `p256-verify` feature.
+#[cfg(feature = "p256-verify")]
+const P256_VERIFY_TRANSITION: BlockNumber = BlockNumber::MAX;
+// native message passer replacing the cross layer witness contract, activated by scheduling a
+// network upgrade together with a state patch seeding the accumulator
+const MESSAGE_PASSER_TRANSITION: BlockNumber = BlockNumber::MAX;
+
 pub fn create_l2_machine() -> EthereumMachine {
-    let params = create_params();
+    create_machine(create_params())
+}
+
+/// Machine of protocol version 1, whose headers carry the rlp encoded `L1Origin` in their extra
+/// data.
+pub fn create_l2_machine_v1() -> EthereumMachine {
+    let mut params = create_params();
+    params.maximum_extra_data_size = 0x80; // fits the rlp encoded L1Origin
+    create_machine(params)
+}
+
+fn create_machine(params: CommonParams) -> EthereumMachine {
     let builtins = create_builtins();
-    EthereumMachine::regular(params, builtins)
+    let mut machine = EthereumMachine::regular(params, builtins);
+    machine.set_state_patches(create_state_patches());
+    machine
+}
+
+// irregular state changes keyed by the L2 block they are applied at, e.g. predeploy upgrades:
+// (number, vec![StatePatch::SetCode { address, code }])
+fn create_state_patches() -> BTreeMap<BlockNumber, Vec<StatePatch>> {
+    BTreeMap::new()
 }
 
 fn create_builtins() -> BTreeMap<Address, Builtin> {
@@ -105,9 +143,96 @@ fn create_builtins() -> BTreeMap<Address, Builtin> {
         },
     );
 
+    #[cfg(feature = "bls12-381")]
+    insert_bls12_381_builtins(&mut map);
+
+    #[cfg(feature = "p256-verify")]
+    map.insert(
+        H160::from_low_u64_be(0x100),
+        Builtin {
+            pricer: BTreeMap::from([(
+                P256_VERIFY_TRANSITION,
+                Pricing::Linear(Linear { base: 3450, word: 0 }),
+            )]),
+            native: EthereumBuiltin::P256Verify(P256Verify),
+        },
+    );
+
+    map.insert(
+        L2_CROSS_LAYER_WITNESS,
+        Builtin {
+            pricer: BTreeMap::from([(
+                MESSAGE_PASSER_TRANSITION,
+                Pricing::Linear(Linear { base: 45000, word: 300 }),
+            )]),
+            native: EthereumBuiltin::MessagePasser(MessagePasser),
+        },
+    );
+
     map
 }
 
+#[cfg(feature = "bls12-381")]
+fn insert_bls12_381_builtins(map: &mut BTreeMap<Address, Builtin>) {
+    let mut address = [0u8; 20];
+    let bls12_381 = [
+        (
+            0x0a,
+            Pricing::Bls12ConstOperations(Bls12ConstOperations { price: 600 }),
+            EthereumBuiltin::Bls12G1Add(Bls12G1Add),
+        ),
+        (
+            0x0b,
+            Pricing::Bls12ConstOperations(Bls12ConstOperations { price: 12000 }),
+            EthereumBuiltin::Bls12G1Mul(Bls12G1Mul),
+        ),
+        (
+            0x0c,
+            Pricing::Bls12MultiexpG1(Bls12MultiexpPricerG1::new(12000)),
+            EthereumBuiltin::Bls12G1MultiExp(Bls12G1MultiExp),
+        ),
+        (
+            0x0d,
+            Pricing::Bls12ConstOperations(Bls12ConstOperations { price: 4500 }),
+            EthereumBuiltin::Bls12G2Add(Bls12G2Add),
+        ),
+        (
+            0x0e,
+            Pricing::Bls12ConstOperations(Bls12ConstOperations { price: 55000 }),
+            EthereumBuiltin::Bls12G2Mul(Bls12G2Mul),
+        ),
+        (
+            0x0f,
+            Pricing::Bls12MultiexpG2(Bls12MultiexpPricerG2::new(55000)),
+            EthereumBuiltin::Bls12G2MultiExp(Bls12G2MultiExp),
+        ),
+        (
+            0x10,
+            Pricing::Bls12Pairing(Bls12PairingPricer {
+                price: Bls12PairingPrice { base: 115000, pair: 23000 },
+            }),
+            EthereumBuiltin::Bls12Pairing(Bls12Pairing),
+        ),
+        (
+            0x11,
+            Pricing::Bls12ConstOperations(Bls12ConstOperations { price: 5500 }),
+            EthereumBuiltin::Bls12MapFpToG1(Bls12MapFpToG1),
+        ),
+        (
+            0x12,
+            Pricing::Bls12ConstOperations(Bls12ConstOperations { price: 110000 }),
+            EthereumBuiltin::Bls12MapFp2ToG2(Bls12MapFp2ToG2),
+        ),
+    ];
+    for (index, pricing, native) in bls12_381 {
+        address[19] = index;
+        map.insert(
+            H160(address),
+            Builtin { pricer: BTreeMap::from([(BLS12_381_TRANSITION, pricing)]), native },
+        );
+    }
+}
+
 fn create_params() -> CommonParams {
     CommonParams {
         account_start_nonce: U256::zero(),
@@ -139,7 +264,6 @@ fn create_params() -> CommonParams {
         eip1706_transition: 0u32.into(),
         eip1884_transition: 0u32.into(),
         eip2028_transition: 0u32.into(),
-        eip2315_transition: BlockNumber::MAX.into(),
         eip2929_transition: BlockNumber::MAX.into(),
         eip2930_transition: 0u32.into(),
         eip3198_transition: BlockNumber::MAX.into(),
@@ -147,6 +271,39 @@ fn create_params() -> CommonParams {
         eip3541_transition: BlockNumber::MAX.into(),
         dust_protection_transition: BlockNumber::MAX.into(),
         eip3607_transition: 0u32.into(),
+        // shanghai rules, activated by scheduling a network upgrade
+        eip3651_transition: BlockNumber::MAX.into(),
+        eip3855_transition: BlockNumber::MAX.into(),
+        eip3860_transition: BlockNumber::MAX.into(),
+        // cancun rules, activated by scheduling a network upgrade
+        eip1153_transition: BlockNumber::MAX.into(),
+        eip5656_transition: BlockNumber::MAX.into(),
+        eip6780_transition: BlockNumber::MAX.into(),
+        // prague rules, activated by scheduling a network upgrade
+        eip7702_transition: BlockNumber::MAX.into(),
+        eip2935_transition: BlockNumber::MAX.into(),
+        eip2935_contract_address: HISTORY_STORAGE_ADDRESS,
+        // l2 rules, activated by scheduling a network upgrade
+        l1_attributes_transition: BlockNumber::MAX.into(),
+        l1_attributes_contract_address: L2_L1_ATTRIBUTES,
+        l1_fee_transition: BlockNumber::MAX.into(),
+        l1_fee_oracle: L2_L1_FEE_ORACLE,
+        l1_fee_vault: L2_L1_FEE_VAULT,
+        tx_data_gas_factor: INTRINSIC_GAS_FACTOR,
+        tx_base_size: TX_BASE_SIZE,
+        // compressed data pricing, activated by scheduling a network upgrade
+        tx_data_compression_transition: BlockNumber::MAX.into(),
+        // fees stay with the block author until split recipients are configured
+        fee_distribution_transition: BlockNumber::MAX.into(),
+        priority_fee_split: Vec::new(),
+        base_fee_split: Vec::new(),
+        l1_fee_split: Vec::new(),
+        enqueued_tx_transition: BlockNumber::MAX.into(),
+        enqueued_gas_limit: L2_ENQUEUED_GAS_LIMIT.into(),
+        // permissioned deployments, enabled and lifted by scheduling network upgrades
+        deployer_allowlist_transition: BlockNumber::MAX.into(),
+        deployer_allowlist_disable_transition: BlockNumber::MAX.into(),
+        deployer_allowlist_contract_address: L2_DEPLOYER_ALLOWLIST,
         nonce_cap_increment: 64u32.into(),
         remove_dust_contracts: false,
         gas_limit_bound_divisor: 0x400u32.into(),
@@ -0,0 +1,67 @@
+use alloc::vec::Vec;
+
+use common_types::hash::keccak;
+use ethereum_types::H256;
+
+/// Version of the output root layout.
+pub const OUTPUT_ROOT_VERSION: u8 = 0;
+
+/// Commitment to the result of a state transition, posted to L1.
+///
+/// The output root is `keccak(version ++ state_root ++ block_hash ++ message_root)`, where
+/// `message_root` is the ordered trie root of the L2 to L1 messages sent in the transition, see
+/// `ethcore::engines::l2_messages_root`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OutputRoot {
+    /// state root of the last block.
+    pub state_root: H256,
+    /// hash of the last block.
+    pub block_hash: H256,
+    /// root of the L2 to L1 messages sent in the transition.
+    pub message_root: H256,
+}
+
+impl OutputRoot {
+    /// Preimage of the output root.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(1 + 32 * 3);
+        data.push(OUTPUT_ROOT_VERSION);
+        data.extend_from_slice(self.state_root.as_bytes());
+        data.extend_from_slice(self.block_hash.as_bytes());
+        data.extend_from_slice(self.message_root.as_bytes());
+        data
+    }
+
+    /// The output root hash.
+    pub fn hash(&self) -> H256 {
+        keccak(self.encode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_root_encoding() {
+        let root = OutputRoot {
+            state_root: H256::repeat_byte(0x11),
+            block_hash: H256::repeat_byte(0x22),
+            message_root: H256::repeat_byte(0x33),
+        };
+        let encoded = root.encode();
+        assert_eq!(encoded.len(), 97);
+        assert_eq!(encoded[0], OUTPUT_ROOT_VERSION);
+        assert_eq!(&encoded[1..33], root.state_root.as_bytes());
+        assert_eq!(&encoded[33..65], root.block_hash.as_bytes());
+        assert_eq!(&encoded[65..], root.message_root.as_bytes());
+        assert_eq!(
+            root.hash(),
+            H256([
+                0x55, 0x4a, 0xfc, 0x28, 0x2c, 0x77, 0xad, 0x77, 0x15, 0xd1, 0x1a, 0x28, 0xca, 0xfa,
+                0xcb, 0x72, 0xb2, 0xf1, 0xa1, 0xa7, 0x17, 0x63, 0xd5, 0x92, 0xf4, 0x9a, 0x12, 0xce,
+                0x92, 0x6b, 0x8c, 0x87,
+            ])
+        );
+    }
+}
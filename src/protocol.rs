@@ -0,0 +1,74 @@
+//! Rule sets of the state transition, selected by the protocol version of the rollup entry.
+//!
+//! A single program image proves transitions of every registered version, so the history
+//! produced under older rules stays provable after an upgrade.
+
+use common_types::l2_cfg::L2_SEQUENCER_REGISTRY;
+use ethcore::engines::SealLayout;
+use ethcore::machine::EthereumMachine;
+use ethcore::miner::TransitionLimits;
+use ethereum_types::Address;
+
+use crate::input::InputFormat;
+use crate::machine;
+
+/// Protocol version of rollup entries which do not carry one.
+pub const INITIAL_PROTOCOL_VERSION: u8 = 0;
+/// Protocol version sealing blocks with `L2SealFields` and recording their L1 origin, bounding
+/// the transition work by `TransitionLimits`, carrying transactions over the block gas limit
+/// into follow-up blocks and requiring batches signed by the sequencers of `L2_SEQUENCER_REGISTRY`
+/// once it registers any.
+pub const PROTOCOL_VERSION_1: u8 = 1;
+
+/// Rules a state transition is executed with.
+pub struct RuleSet {
+    /// protocol version of the rules.
+    pub version: u8,
+    /// builds the L2 state machine.
+    pub machine: fn() -> EthereumMachine,
+    /// seal layout of the produced blocks.
+    pub seal: SealLayout,
+    /// whether block headers record the `L1Origin` of their batch in the extra data.
+    pub l1_origin: bool,
+    /// format of the rollup input.
+    pub input: InputFormat,
+    /// resource limits of a transition.
+    pub limits: TransitionLimits,
+    /// system contract registering the sequencers authorized to sign batches in the state of the
+    /// previous block. Batches need no signature without registry or while it is empty.
+    pub sequencer_registry: Option<Address>,
+    /// whether transactions over the block gas limit are carried over into follow-up blocks.
+    pub carry_over: bool,
+    /// whether each block of a transition sees the hashes of the blocks produced before it,
+    /// instead of the ancestors of the previous block only.
+    pub chain_last_hashes: bool,
+}
+
+/// Returns the rule set of `version`, or None if the version is unknown.
+pub fn rule_set(version: u8) -> Option<RuleSet> {
+    match version {
+        INITIAL_PROTOCOL_VERSION => Some(RuleSet {
+            version,
+            machine: machine::create_l2_machine,
+            seal: SealLayout::Legacy,
+            l1_origin: false,
+            input: InputFormat::Legacy,
+            limits: TransitionLimits::unbounded(),
+            sequencer_registry: None,
+            carry_over: false,
+            chain_last_hashes: false,
+        }),
+        PROTOCOL_VERSION_1 => Some(RuleSet {
+            version,
+            machine: machine::create_l2_machine_v1,
+            seal: SealLayout::Fields,
+            l1_origin: true,
+            input: InputFormat::Bounded,
+            limits: TransitionLimits::default(),
+            sequencer_registry: Some(L2_SEQUENCER_REGISTRY),
+            carry_over: true,
+            chain_last_hashes: true,
+        }),
+        _ => None,
+    }
+}